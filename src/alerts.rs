@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Uma regra de alerta em `config.toml`:
+///
+/// ```toml
+/// [[alerts]]
+/// rule = "cpu > 90 for 30s"
+/// bell = true
+/// command = "notify-send \"CPU at $MONITOR_ALERT_VALUE%\""
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertConfig {
+    pub rule: String,
+    #[serde(default)]
+    pub bell: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Quanto o valor precisa voltar além do limite para o alerta sair
+    #[serde(default = "default_hysteresis")]
+    pub hysteresis: f64,
+}
+
+fn default_hysteresis() -> f64 {
+    5.0
+}
+
+/// Valores que uma regra pode observar.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Metric {
+    Cpu,
+    Memory,
+    Swap,
+    Gpu,
+    GpuMemory,
+    GpuTemp,
+    /// PSI de memória (`some` e `full`, média de 10s)
+    MemoryPressure,
+    MemoryPressureFull,
+    /// % dos períodos em que o cgroup foi estrangulado pela cota de CPU
+    CpuThrottled,
+    /// Uso do sistema de arquivos montado nesse caminho
+    Disk(String),
+}
+
+impl Metric {
+    // Métricas passadas ao comando em variáveis de ambiente
+    const ENV: [(Metric, &'static str); 9] = [
+        (Metric::Cpu, "MONITOR_CPU"),
+        (Metric::Memory, "MONITOR_MEMORY"),
+        (Metric::Swap, "MONITOR_SWAP"),
+        (Metric::Gpu, "MONITOR_GPU"),
+        (Metric::GpuMemory, "MONITOR_GPU_MEMORY"),
+        (Metric::GpuTemp, "MONITOR_GPU_TEMP"),
+        (Metric::MemoryPressure, "MONITOR_MEMORY_PRESSURE"),
+        (Metric::MemoryPressureFull, "MONITOR_MEMORY_PRESSURE_FULL"),
+        (Metric::CpuThrottled, "MONITOR_CPU_THROTTLED"),
+    ];
+
+    fn parse(name: &str) -> Option<Metric> {
+        if let Some(mount) = name.strip_prefix("disk:") {
+            return (!mount.is_empty()).then(|| Metric::Disk(mount.to_string()));
+        }
+        match name {
+            "cpu" => Some(Metric::Cpu),
+            "mem" | "memory" => Some(Metric::Memory),
+            "swap" => Some(Metric::Swap),
+            "gpu" => Some(Metric::Gpu),
+            "gpu_mem" | "gpu_memory" => Some(Metric::GpuMemory),
+            "gpu_temp" => Some(Metric::GpuTemp),
+            "mem_pressure" | "memory_pressure" => Some(Metric::MemoryPressure),
+            "mem_pressure_full" | "memory_pressure_full" => Some(Metric::MemoryPressureFull),
+            "cpu_throttled" => Some(Metric::CpuThrottled),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Metric::Cpu => "cpu".to_string(),
+            Metric::Memory => "memory".to_string(),
+            Metric::Swap => "swap".to_string(),
+            Metric::Gpu => "gpu".to_string(),
+            Metric::GpuMemory => "gpu_memory".to_string(),
+            Metric::GpuTemp => "gpu_temp".to_string(),
+            Metric::MemoryPressure => "memory_pressure".to_string(),
+            Metric::MemoryPressureFull => "memory_pressure_full".to_string(),
+            Metric::CpuThrottled => "cpu_throttled".to_string(),
+            Metric::Disk(mount) => format!("disk:{}", mount),
+        }
+    }
+}
+
+/// `<métrica> <op> <limite> [for <duração>]`, já interpretada.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub metric: Metric,
+    above: bool,
+    threshold: f64,
+    duration: Duration,
+}
+
+impl Rule {
+    pub fn parse(text: &str) -> Result<Rule, String> {
+        let invalid = |reason: &str| format!("invalid alert rule \"{}\": {}", text, reason);
+
+        let op = text
+            .find(['>', '<'])
+            .ok_or_else(|| invalid("expected `>` or `<`"))?;
+        let above = text[op..].starts_with('>');
+        let metric = text[..op].trim();
+        let metric = Metric::parse(metric)
+            .ok_or_else(|| invalid(&format!("unknown metric `{}`", metric)))?;
+
+        let rest = text[op + 1..].trim();
+        let (threshold, duration) = match rest.split_once(" for ") {
+            Some((threshold, duration)) => (
+                threshold.trim(),
+                parse_duration(duration.trim())
+                    .ok_or_else(|| invalid(&format!("bad duration `{}`", duration.trim())))?,
+            ),
+            None => (rest, Duration::ZERO),
+        };
+        let threshold = threshold
+            .trim_end_matches('%')
+            .parse()
+            .map_err(|_| invalid(&format!("bad threshold `{}`", threshold)))?;
+
+        Ok(Rule {
+            metric,
+            above,
+            threshold,
+            duration,
+        })
+    }
+
+    fn crossed(&self, value: f64) -> bool {
+        if self.above {
+            value > self.threshold
+        } else {
+            value < self.threshold
+        }
+    }
+
+    /// O valor voltou para o lado seguro com folga de `hysteresis`.
+    fn recovered(&self, value: f64, hysteresis: f64) -> bool {
+        if self.above {
+            value <= self.threshold - hysteresis
+        } else {
+            value >= self.threshold + hysteresis
+        }
+    }
+}
+
+/// `500ms`, `30s`, `5m`, `1h` ou segundos sem unidade.
+fn parse_duration(text: &str) -> Option<Duration> {
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let value: f64 = text[..split].parse().ok()?;
+    let seconds = match &text[split..] {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Clear,
+    // Limite ultrapassado, esperando completar a duração da regra
+    Pending(Instant),
+    Firing,
+}
+
+pub struct Alert {
+    pub config: AlertConfig,
+    pub rule: Rule,
+    state: State,
+    pub value: Option<f64>,
+}
+
+impl Alert {
+    pub fn is_firing(&self) -> bool {
+        self.state == State::Firing
+    }
+
+    /// Toca o sino e/ou roda o comando configurado.
+    fn trigger(&self, value_of: &dyn Fn(&Metric) -> Option<f64>) {
+        if self.config.bell {
+            // stderr para não misturar com a saída JSON do modo headless
+            let _ = io::stderr().write_all(b"\x07");
+        }
+
+        let Some(command) = &self.config.command else {
+            return;
+        };
+        let mut process = if cfg!(target_os = "windows") {
+            let mut process = Command::new("cmd");
+            process.arg("/C");
+            process
+        } else {
+            let mut process = Command::new("sh");
+            process.arg("-c");
+            process
+        };
+        process
+            .arg(command)
+            .env("MONITOR_ALERT_RULE", &self.config.rule)
+            .env("MONITOR_ALERT_METRIC", self.rule.metric.name())
+            .env("MONITOR_ALERT_THRESHOLD", self.rule.threshold.to_string())
+            .env(
+                "MONITOR_ALERT_VALUE",
+                self.value.map(|v| format!("{:.1}", v)).unwrap_or_default(),
+            );
+        for (metric, name) in &Metric::ENV {
+            if let Some(value) = value_of(metric) {
+                process.env(name, format!("{:.1}", value));
+            }
+        }
+
+        match process.spawn() {
+            // Esperar em outra thread para não bloquear a coleta nem deixar zumbis
+            Ok(mut child) => {
+                thread::spawn(move || child.wait());
+            }
+            Err(e) => eprintln!("Error running alert command `{}`: {}", command, e),
+        }
+    }
+}
+
+/// Avalia as regras de alerta a cada amostra.
+#[derive(Default)]
+pub struct Alerts {
+    alerts: Vec<Alert>,
+}
+
+impl Alerts {
+    pub fn new(configs: &[AlertConfig]) -> Result<Self, String> {
+        let alerts = configs
+            .iter()
+            .map(|config| {
+                Ok(Alert {
+                    rule: Rule::parse(&config.rule)?,
+                    config: config.clone(),
+                    state: State::Clear,
+                    value: None,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { alerts })
+    }
+
+    /// Atualiza o estado de cada regra e retorna as que acabaram de disparar.
+    fn evaluate(&mut self, now: Instant, value_of: &dyn Fn(&Metric) -> Option<f64>) -> Vec<usize> {
+        let mut fired = Vec::new();
+        for (i, alert) in self.alerts.iter_mut().enumerate() {
+            alert.value = value_of(&alert.rule.metric);
+            // Sem valor (ex.: disco desmontado) a regra fica como está
+            let Some(value) = alert.value else {
+                continue;
+            };
+
+            alert.state = match alert.state {
+                State::Firing if alert.rule.recovered(value, alert.config.hysteresis) => {
+                    State::Clear
+                }
+                State::Firing => State::Firing,
+                _ if !alert.rule.crossed(value) => State::Clear,
+                State::Clear => State::Pending(now),
+                pending => pending,
+            };
+            if let State::Pending(since) = alert.state {
+                if now.duration_since(since) >= alert.rule.duration {
+                    alert.state = State::Firing;
+                    fired.push(i);
+                }
+            }
+        }
+        fired
+    }
+
+    pub fn update(&mut self, value_of: &dyn Fn(&Metric) -> Option<f64>) {
+        for i in self.evaluate(Instant::now(), value_of) {
+            self.alerts[i].trigger(value_of);
+        }
+    }
+
+    pub fn firing(&self) -> impl Iterator<Item = &Alert> {
+        self.alerts.iter().filter(|alert| alert.is_firing())
+    }
+
+    /// Alguma regra observa uma métrica que satisfaz `matches`?
+    pub fn watches(&self, matches: impl Fn(&Metric) -> bool) -> bool {
+        self.alerts.iter().any(|alert| matches(&alert.rule.metric))
+    }
+
+    /// Há algum alerta ativo sobre uma métrica que satisfaz `matches`?
+    pub fn any_firing(&self, matches: impl Fn(&Metric) -> bool) -> bool {
+        self.firing().any(|alert| matches(&alert.rule.metric))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alerts(rule: &str) -> Alerts {
+        Alerts::new(&[AlertConfig {
+            rule: rule.to_string(),
+            bell: false,
+            command: None,
+            hysteresis: default_hysteresis(),
+        }])
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_rules() {
+        let rule = Rule::parse("cpu > 90 for 30s").unwrap();
+        assert_eq!(rule.metric, Metric::Cpu);
+        assert!(rule.above);
+        assert_eq!(rule.threshold, 90.0);
+        assert_eq!(rule.duration, Duration::from_secs(30));
+
+        let rule = Rule::parse("disk:/ > 95").unwrap();
+        assert_eq!(rule.metric, Metric::Disk("/".to_string()));
+        assert_eq!(rule.duration, Duration::ZERO);
+        assert!(alerts("disk:/ > 95").watches(|metric| matches!(metric, Metric::Disk(_))));
+        assert!(!alerts("cpu > 90").watches(|metric| matches!(metric, Metric::Disk(_))));
+
+        assert_eq!(Rule::parse("gpu_temp>85").unwrap().threshold, 85.0);
+        assert_eq!(
+            Rule::parse("mem_pressure_full > 10 for 1m").unwrap().metric,
+            Metric::MemoryPressureFull
+        );
+        assert_eq!(
+            Rule::parse("cpu_throttled > 25 for 10s").unwrap().metric,
+            Metric::CpuThrottled
+        );
+        assert!(Rule::parse("cpu 90").is_err());
+        assert!(Rule::parse("load > 2").is_err());
+        assert!(Rule::parse("cpu > 90 for ever").is_err());
+        assert!(Rule::parse("cpu > 90 for 99999999999999999999h").is_err());
+    }
+
+    #[test]
+    fn fires_only_after_the_duration() {
+        let mut alerts = alerts("cpu > 90 for 30s");
+        let start = Instant::now();
+
+        assert!(alerts.evaluate(start, &|_| Some(95.0)).is_empty());
+        assert!(alerts
+            .evaluate(start + Duration::from_secs(10), &|_| Some(95.0))
+            .is_empty());
+        assert_eq!(
+            alerts.evaluate(start + Duration::from_secs(30), &|_| Some(95.0)),
+            vec![0]
+        );
+        // Já disparado: não dispara de novo
+        assert!(alerts
+            .evaluate(start + Duration::from_secs(40), &|_| Some(95.0))
+            .is_empty());
+        assert_eq!(alerts.firing().count(), 1);
+    }
+
+    #[test]
+    fn dipping_below_restarts_the_duration() {
+        let mut alerts = alerts("cpu > 90 for 30s");
+        let start = Instant::now();
+
+        alerts.evaluate(start, &|_| Some(95.0));
+        alerts.evaluate(start + Duration::from_secs(20), &|_| Some(50.0));
+        assert!(alerts
+            .evaluate(start + Duration::from_secs(31), &|_| Some(95.0))
+            .is_empty());
+        assert_eq!(
+            alerts.evaluate(start + Duration::from_secs(61), &|_| Some(95.0)),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn clears_with_hysteresis() {
+        let mut alerts = alerts("swap > 50");
+        let now = Instant::now();
+
+        assert_eq!(alerts.evaluate(now, &|_| Some(51.0)), vec![0]);
+        // Oscilando em volta do limite continua ativo
+        alerts.evaluate(now, &|_| Some(49.0));
+        alerts.evaluate(now, &|_| Some(50.5));
+        assert_eq!(alerts.firing().count(), 1);
+
+        alerts.evaluate(now, &|_| Some(45.0));
+        assert_eq!(alerts.firing().count(), 0);
+    }
+}
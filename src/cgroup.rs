@@ -0,0 +1,929 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use humansize::{format_size, BINARY};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tui::{
+    layout::Constraint,
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+};
+
+use crate::sysfs::{read_number, read_string};
+
+/// `--cgroup`: limites do próprio cgroup, de um cgroup escolhido, ou nenhum.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum CgroupMode {
+    /// Usa os limites do cgroup do monitor, se houver algum
+    #[default]
+    Auto,
+    Off,
+    /// Caminho dentro de /sys/fs/cgroup (ex.: `system.slice/docker-<id>.scope`) ou absoluto
+    Path(PathBuf),
+}
+
+impl From<String> for CgroupMode {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "auto" => CgroupMode::Auto,
+            "off" => CgroupMode::Off,
+            _ => CgroupMode::Path(PathBuf::from(value)),
+        }
+    }
+}
+
+impl From<CgroupMode> for String {
+    fn from(mode: CgroupMode) -> Self {
+        match mode {
+            CgroupMode::Auto => "auto".to_string(),
+            CgroupMode::Off => "off".to_string(),
+            CgroupMode::Path(path) => path.to_string_lossy().to_string(),
+        }
+    }
+}
+
+/// Usado pelo clap para `--cgroup`.
+pub fn parse_mode(value: &str) -> Result<CgroupMode, String> {
+    if value.is_empty() {
+        return Err("expected auto, off or a cgroup path".to_string());
+    }
+    Ok(CgroupMode::from(value.to_string()))
+}
+
+/// Leitura de um cgroup; percentuais já calculados contra os limites dele.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CgroupStats {
+    /// Caminho relativo à raiz do cgroupfs, ex.: `/system.slice/docker-1a2b.scope`
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_max: Option<u64>,
+    /// Memória em uso sem o cache inativo (o que conta para o OOM); no modo auto, só com limite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_used: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swap_max: Option<u64>,
+    pub swap_used: u64,
+    /// Cota de CPU em núcleos (`cpu.max`), se limitada
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_quota: Option<f64>,
+    /// Uso de CPU em % da cota (ou de todos os núcleos, sem cota e fora do modo auto)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<f64>,
+    /// % dos períodos do último intervalo em que o cgroup foi estrangulado
+    pub throttled_percent: f64,
+    pub throttled_periods: u64,
+    /// Tempo total estrangulado, em segundos
+    pub throttled_seconds: f64,
+}
+
+impl CgroupStats {
+    pub fn has_limits(&self) -> bool {
+        self.memory_max.is_some() || self.cpu_quota.is_some()
+    }
+}
+
+/// `cpu.max`: `<cota> <período>` em µs, ou `max <período>`.
+fn read_cpu_quota(dir: &Path) -> Option<f64> {
+    let cpu_max = read_string(&dir.join("cpu.max"))?;
+    let (quota, period) = cpu_max.split_once(' ')?;
+    let (quota, period): (f64, f64) = (quota.parse().ok()?, period.parse().ok()?);
+    (period > 0.0).then(|| quota / period)
+}
+
+/// Campo `<chave> <valor>` de arquivos como `cpu.stat` e `memory.stat`.
+pub fn stat_field(contents: &str, key: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        (name == key).then(|| value.trim().parse().ok())?
+    })
+}
+
+/// Caminho do cgroup v2 na linha `0::<caminho>` de /proc/self/cgroup.
+fn own_cgroup(proc_self_cgroup: &str) -> Option<PathBuf> {
+    proc_self_cgroup
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(|path| PathBuf::from(path.trim_start_matches('/')))
+}
+
+/// Acompanha um cgroup v2: limites herdados dos ancestrais e contadores de CPU.
+pub struct CgroupTracker {
+    root: PathBuf,
+    dir: PathBuf,
+    /// No modo auto, só vale se algum limite existir
+    auto: bool,
+    last_cpu: Option<(u64, u64, u64, Instant)>,
+}
+
+impl CgroupTracker {
+    /// `sysfs_root/fs/cgroup` precisa ser cgroup v2 (ter `cgroup.controllers`).
+    pub fn new(mode: &CgroupMode, sysfs_root: &Path) -> Option<Self> {
+        let own = fs::read_to_string("/proc/self/cgroup").unwrap_or_default();
+        Self::with_own_cgroup(mode, &sysfs_root.join("fs/cgroup"), &own)
+    }
+
+    fn with_own_cgroup(mode: &CgroupMode, root: &Path, proc_self_cgroup: &str) -> Option<Self> {
+        if !root.join("cgroup.controllers").exists() {
+            return None;
+        }
+        let dir = match mode {
+            CgroupMode::Off => return None,
+            // Num container com namespace de cgroup o caminho é `/`, a raiz montada
+            CgroupMode::Auto => own_cgroup(proc_self_cgroup)
+                .map(|own| root.join(own))
+                .filter(|dir| dir.is_dir())
+                .unwrap_or_else(|| root.to_path_buf()),
+            CgroupMode::Path(path) if path.starts_with(root) => path.clone(),
+            CgroupMode::Path(path) => root.join(path.strip_prefix("/").unwrap_or(path)),
+        };
+        if !dir.is_dir() {
+            return None;
+        }
+        Some(Self {
+            root: root.to_path_buf(),
+            dir,
+            auto: *mode == CgroupMode::Auto,
+            last_cpu: None,
+        })
+    }
+
+    /// O cgroup e seus ancestrais até a raiz, do mais próximo ao mais distante.
+    fn chain(&self) -> Vec<PathBuf> {
+        self.dir
+            .ancestors()
+            .take_while(|dir| dir.starts_with(&self.root))
+            .map(Path::to_path_buf)
+            .collect()
+    }
+
+    /// O menor limite da cadeia e o cgroup que o define.
+    fn tightest<T: PartialOrd>(&self, read: impl Fn(&Path) -> Option<T>) -> Option<(T, PathBuf)> {
+        self.chain()
+            .into_iter()
+            .filter_map(|dir| Some((read(&dir)?, dir)))
+            .fold(
+                None,
+                |best: Option<(T, PathBuf)>, (value, dir)| match best {
+                    Some((best, best_dir)) if best <= value => Some((best, best_dir)),
+                    _ => Some((value, dir)),
+                },
+            )
+    }
+
+    /// Lê o cgroup; `None` no modo auto quando nenhum limite se aplica.
+    /// Sem cota de CPU, o uso é relativo a `host_cpus` núcleos.
+    pub fn update(&mut self, host_cpus: usize) -> Option<CgroupStats> {
+        self.update_at(host_cpus, Instant::now())
+    }
+
+    fn update_at(&mut self, host_cpus: usize, now: Instant) -> Option<CgroupStats> {
+        // `max` (sem limite) não é um número e vira `None`
+        let memory = self.tightest(|dir| read_number(&dir.join("memory.max")));
+        let swap = self.tightest(|dir| read_number(&dir.join("memory.swap.max")));
+        let cpu = self.tightest(read_cpu_quota);
+        if self.auto && memory.is_none() && cpu.is_none() {
+            return None;
+        }
+
+        // No modo auto o uso vem de quem define o limite (ex.: o container, não o shell dentro dele)
+        let memory_dir = memory
+            .as_ref()
+            .filter(|_| self.auto)
+            .map_or(self.dir.clone(), |(_, dir)| dir.clone());
+        let cpu_dir = cpu
+            .as_ref()
+            .filter(|_| self.auto)
+            .map_or(self.dir.clone(), |(_, dir)| dir.clone());
+
+        let inactive = read_string(&memory_dir.join("memory.stat"))
+            .and_then(|stat| stat_field(&stat, "inactive_file"))
+            .unwrap_or(0);
+        let memory_used = read_number(&memory_dir.join("memory.current"))
+            .map(|current| current.saturating_sub(inactive))
+            .filter(|_| memory.is_some() || !self.auto);
+
+        let cpu_stat = read_string(&cpu_dir.join("cpu.stat")).unwrap_or_default();
+        let usage = stat_field(&cpu_stat, "usage_usec").unwrap_or(0);
+        let periods = stat_field(&cpu_stat, "nr_periods").unwrap_or(0);
+        let throttled = stat_field(&cpu_stat, "nr_throttled").unwrap_or(0);
+        let throttled_usec = stat_field(&cpu_stat, "throttled_usec").unwrap_or(0);
+
+        let cpu_quota = cpu.map(|(quota, _)| quota);
+        let capacity = cpu_quota.unwrap_or(host_cpus.max(1) as f64);
+        let (cpu_percent, throttled_percent) = match self.last_cpu {
+            Some((last_usage, last_periods, last_throttled, then)) => {
+                let seconds = now.duration_since(then).as_secs_f64();
+                let busy = usage.saturating_sub(last_usage) as f64 / 1e6;
+                let periods = periods.saturating_sub(last_periods);
+                (
+                    (seconds > 0.0 && (cpu_quota.is_some() || !self.auto))
+                        .then(|| busy / seconds / capacity * 100.0),
+                    if periods > 0 {
+                        throttled.saturating_sub(last_throttled) as f64 / periods as f64 * 100.0
+                    } else {
+                        0.0
+                    },
+                )
+            }
+            None => (None, 0.0),
+        };
+        self.last_cpu = Some((usage, periods, throttled, now));
+
+        Some(CgroupStats {
+            path: format!(
+                "/{}",
+                self.dir
+                    .strip_prefix(&self.root)
+                    .unwrap_or(&self.dir)
+                    .display()
+            ),
+            memory_max: memory.map(|(max, _)| max),
+            memory_used,
+            swap_max: swap.map(|(max, _)| max),
+            swap_used: read_number(&memory_dir.join("memory.swap.current")).unwrap_or(0),
+            cpu_quota,
+            cpu_percent,
+            throttled_percent,
+            throttled_periods: throttled,
+            throttled_seconds: throttled_usec as f64 / 1e6,
+        })
+    }
+}
+
+/// Uso de um cgroup no painel, com as taxas desde a varredura anterior.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CgroupUsage {
+    /// Caminho relativo à raiz, ex.: `/system.slice/docker-1a2b….scope`
+    pub path: String,
+    /// Nome curto: `docker:1a2b3c4d5e6f`, `pod:0c8e1f2a`, `nginx.service`
+    pub name: String,
+    pub kind: &'static str,
+    /// % de um núcleo, como na tabela de processos
+    pub cpu_percent: f64,
+    pub memory_current: u64,
+    pub memory_max: Option<u64>,
+    pub io_read_rate: f64,
+    pub io_write_rate: f64,
+    pub pids: u64,
+}
+
+/// Prefixos de escopos de containers criados pelo systemd, do mais específico ao mais geral.
+const CONTAINER_SCOPES: [(&str, &str); 6] = [
+    ("docker-", "docker"),
+    ("libpod-conmon-", "conmon"),
+    ("libpod-", "podman"),
+    ("cri-containerd-", "containerd"),
+    ("crio-conmon-", "conmon"),
+    ("crio-", "crio"),
+];
+
+fn is_container_id(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Nome curto e tipo de um cgroup a partir do nome do diretório e do caminho do pai.
+pub fn describe(name: &str, parent: &str) -> (String, &'static str) {
+    let short = |id: &str| id.chars().take(12).collect::<String>();
+    if let Some(id) = name.strip_suffix(".scope") {
+        for (prefix, runtime) in CONTAINER_SCOPES {
+            if let Some(id) = id.strip_prefix(prefix).filter(|id| is_container_id(id)) {
+                return (format!("{}:{}", runtime, short(id)), runtime);
+            }
+        }
+    }
+    // Driver cgroupfs: /docker/<id>, /kubepods/burstable/pod<uid>/<id>
+    if is_container_id(name) {
+        let runtime = match parent.rsplit('/').next() {
+            Some("docker") => "docker",
+            _ => "container",
+        };
+        return (format!("{}:{}", runtime, short(name)), runtime);
+    }
+    // kubepods-burstable-pod<uid com _>.slice, ou pod<uid> com o driver cgroupfs
+    let pod = name
+        .strip_suffix(".slice")
+        .and_then(|slice| slice.rsplit_once("-pod"))
+        .filter(|(qos, _)| qos.starts_with("kubepods"))
+        .map(|(_, uid)| uid)
+        .or_else(|| {
+            name.strip_prefix("pod")
+                .filter(|_| parent.contains("kubepods"))
+        });
+    if let Some(uid) = pod {
+        return (
+            format!("pod:{}", uid.chars().take(8).collect::<String>()),
+            "pod",
+        );
+    }
+    let kind = match name.rsplit_once('.') {
+        Some((_, "slice")) => "slice",
+        Some((_, "service")) => "service",
+        Some((_, "scope")) => "scope",
+        _ => "cgroup",
+    };
+    (name.to_string(), kind)
+}
+
+/// Bytes lidos e escritos somando todos os dispositivos de `io.stat`.
+pub fn parse_io_stat(contents: &str) -> (u64, u64) {
+    let field = |key: &str| -> u64 {
+        contents
+            .split_whitespace()
+            .filter_map(|field| field.strip_prefix(key)?.parse::<u64>().ok())
+            .sum()
+    };
+    (field("rbytes="), field("wbytes="))
+}
+
+/// Percorre a árvore de cgroups guardando os contadores para calcular taxas.
+pub struct CgroupScanner {
+    root: PathBuf,
+    previous: HashMap<String, (u64, u64, u64)>,
+    last: Option<Instant>,
+}
+
+impl CgroupScanner {
+    pub fn new(sysfs_root: &Path) -> Self {
+        Self {
+            root: sysfs_root.join("fs/cgroup"),
+            previous: HashMap::new(),
+            last: None,
+        }
+    }
+
+    /// Todos os cgroups abaixo da raiz; vazio sem cgroup v2.
+    pub fn scan(&mut self) -> Vec<CgroupUsage> {
+        self.scan_at(Instant::now())
+    }
+
+    fn scan_at(&mut self, now: Instant) -> Vec<CgroupUsage> {
+        let seconds = self
+            .last
+            .map_or(0.0, |last| now.duration_since(last).as_secs_f64());
+        let mut counters = HashMap::new();
+        let mut usage = Vec::new();
+
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut children: Vec<PathBuf> = entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+                .map(|entry| entry.path())
+                .collect();
+            children.sort();
+            pending.extend(children.into_iter().rev());
+            if dir == self.root {
+                continue;
+            }
+
+            let path = format!(
+                "/{}",
+                dir.strip_prefix(&self.root).unwrap_or(&dir).display()
+            );
+            let (parent, file_name) = path.rsplit_once('/').unwrap_or_default();
+            let (name, kind) = describe(file_name, parent);
+
+            let cpu = read_string(&dir.join("cpu.stat"))
+                .and_then(|stat| stat_field(&stat, "usage_usec"))
+                .unwrap_or(0);
+            let (read, written) = read_string(&dir.join("io.stat"))
+                .map(|stat| parse_io_stat(&stat))
+                .unwrap_or_default();
+            let rate = |now: u64, before: u64| {
+                if seconds > 0.0 {
+                    now.saturating_sub(before) as f64 / seconds
+                } else {
+                    0.0
+                }
+            };
+            // Um cgroup novo começa sem taxa, em vez de contar tudo desde a criação
+            let (cpu_before, read_before, written_before) = self
+                .previous
+                .get(&path)
+                .copied()
+                .unwrap_or((cpu, read, written));
+
+            usage.push(CgroupUsage {
+                name,
+                kind,
+                cpu_percent: rate(cpu, cpu_before) / 1e6 * 100.0,
+                memory_current: read_number(&dir.join("memory.current")).unwrap_or(0),
+                memory_max: read_number(&dir.join("memory.max")),
+                io_read_rate: rate(read, read_before),
+                io_write_rate: rate(written, written_before),
+                pids: read_number(&dir.join("pids.current")).unwrap_or(0),
+                path: path.clone(),
+            });
+            counters.insert(path, (cpu, read, written));
+        }
+
+        self.previous = counters;
+        self.last = Some(now);
+        usage
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SortColumn {
+    Name,
+    Kind,
+    Cpu,
+    Memory,
+    Read,
+    Write,
+    Pids,
+    Path,
+}
+
+impl SortColumn {
+    const ALL: [SortColumn; 8] = [
+        SortColumn::Name,
+        SortColumn::Kind,
+        SortColumn::Cpu,
+        SortColumn::Memory,
+        SortColumn::Read,
+        SortColumn::Write,
+        SortColumn::Pids,
+        SortColumn::Path,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            SortColumn::Name => "Name",
+            SortColumn::Kind => "Type",
+            SortColumn::Cpu => "CPU%",
+            SortColumn::Memory => "Memory",
+            SortColumn::Read => "Read/s",
+            SortColumn::Write => "Write/s",
+            SortColumn::Pids => "PIDs",
+            SortColumn::Path => "Path",
+        }
+    }
+
+    fn compare(self, a: &CgroupUsage, b: &CgroupUsage) -> Ordering {
+        match self {
+            SortColumn::Name => a.name.cmp(&b.name),
+            SortColumn::Kind => a.kind.cmp(b.kind),
+            SortColumn::Cpu => a.cpu_percent.total_cmp(&b.cpu_percent),
+            SortColumn::Memory => a.memory_current.cmp(&b.memory_current),
+            SortColumn::Read => a.io_read_rate.total_cmp(&b.io_read_rate),
+            SortColumn::Write => a.io_write_rate.total_cmp(&b.io_write_rate),
+            SortColumn::Pids => a.pids.cmp(&b.pids),
+            SortColumn::Path => a.path.cmp(&b.path),
+        }
+    }
+}
+
+static WIDTHS: [Constraint; 8] = [
+    Constraint::Length(20),
+    Constraint::Length(10),
+    Constraint::Length(7),
+    Constraint::Length(21),
+    Constraint::Length(11),
+    Constraint::Length(11),
+    Constraint::Length(6),
+    Constraint::Min(20),
+];
+
+/// Painel de cgroups: ordenação, filtro e o cgroup escolhido para o gráfico.
+pub struct CgroupTable {
+    rows: Vec<CgroupUsage>,
+    sort: SortColumn,
+    descending: bool,
+    filter: String,
+    filtering: bool,
+    /// Caminho do cgroup aberto com Enter
+    drilled: Option<String>,
+    pub state: TableState,
+}
+
+impl CgroupTable {
+    pub fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            sort: SortColumn::Cpu,
+            descending: true,
+            filter: String::new(),
+            filtering: false,
+            drilled: None,
+            state: TableState::default(),
+        }
+    }
+
+    pub fn refresh(&mut self, rows: Vec<CgroupUsage>) {
+        // Manter a seleção no mesmo cgroup entre atualizações
+        let selected = self.selected().map(|row| row.path.clone());
+        self.rows = rows;
+        self.sort_rows();
+
+        let visible = self.visible();
+        let index = selected
+            .and_then(|path| visible.iter().position(|row| row.path == path))
+            .or(if visible.is_empty() { None } else { Some(0) });
+        self.state.select(index);
+    }
+
+    /// O cgroup aberto no gráfico, se ainda existir.
+    pub fn drilled(&self) -> Option<&CgroupUsage> {
+        let path = self.drilled.as_ref()?;
+        self.rows.iter().find(|row| &row.path == path)
+    }
+
+    pub fn is_drilled(&self) -> bool {
+        self.drilled.is_some()
+    }
+
+    fn sort_rows(&mut self) {
+        let (sort, descending) = (self.sort, self.descending);
+        self.rows.sort_by(|a, b| {
+            let ordering = sort.compare(a, b);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    fn visible(&self) -> Vec<&CgroupUsage> {
+        let filter = self.filter.to_lowercase();
+        self.rows
+            .iter()
+            .filter(|row| {
+                filter.is_empty()
+                    || row.name.to_lowercase().contains(&filter)
+                    || row.path.to_lowercase().contains(&filter)
+            })
+            .collect()
+    }
+
+    fn selected(&self) -> Option<&CgroupUsage> {
+        self.visible().get(self.state.selected()?).copied()
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.visible().len();
+        if len == 0 {
+            self.state.select(None);
+            return;
+        }
+        let current = self.state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, len as isize - 1);
+        self.state.select(Some(next as usize));
+    }
+
+    fn set_sort(&mut self, column: SortColumn) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = !matches!(
+                column,
+                SortColumn::Name | SortColumn::Kind | SortColumn::Path
+            );
+        }
+        self.sort_rows();
+    }
+
+    /// Trata uma tecla; retorna `false` se ela não pertence ao painel.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.filtering {
+            match key.code {
+                KeyCode::Char(c) => self.filter.push(c),
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.filtering = false;
+                }
+                KeyCode::Enter => self.filtering = false,
+                _ => {}
+            }
+            self.move_selection(0);
+            return true;
+        }
+        match key.code {
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Home => self.move_selection(isize::MIN / 2),
+            KeyCode::End => self.move_selection(isize::MAX / 2),
+            KeyCode::Char('/') => self.filtering = true,
+            KeyCode::Char(c @ '1'..='8') => {
+                self.set_sort(SortColumn::ALL[c as usize - '1' as usize])
+            }
+            KeyCode::Enter => self.drilled = self.selected().map(|row| row.path.clone()),
+            KeyCode::Esc if self.drilled.is_some() => self.drilled = None,
+            _ => return false,
+        }
+        true
+    }
+
+    fn title(&self) -> String {
+        if self.filtering {
+            return format!("Cgroups - filter: {}_", self.filter);
+        }
+        let mut title = format!(
+            "Cgroups ({}) - sort: {} {}",
+            self.visible().len(),
+            self.sort.title(),
+            if self.descending { "▼" } else { "▲" }
+        );
+        if !self.filter.is_empty() {
+            title.push_str(&format!(" - filter: {}", self.filter));
+        }
+        if let Some(row) = self.drilled() {
+            title.push_str(&format!(" - charting {}", row.name));
+        }
+        title
+    }
+
+    pub fn widget(&self) -> Table<'static> {
+        let header = Row::new(SortColumn::ALL.iter().enumerate().map(|(i, column)| {
+            let style = if *column == self.sort {
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::LightBlue)
+            };
+            Cell::from(format!("{} {}", i + 1, column.title())).style(style)
+        }));
+
+        let rate = |bytes: f64| format!("{}/s", format_size(bytes as u64, BINARY));
+        let rows: Vec<Row> = self
+            .visible()
+            .into_iter()
+            .map(|row| {
+                let memory = match row.memory_max {
+                    Some(max) => format!(
+                        "{} / {}",
+                        format_size(row.memory_current, BINARY),
+                        format_size(max, BINARY)
+                    ),
+                    None => format_size(row.memory_current, BINARY),
+                };
+                let style = if Some(&row.path) == self.drilled.as_ref() {
+                    Style::default().fg(Color::LightBlue)
+                } else {
+                    Style::default()
+                };
+                Row::new(vec![
+                    Cell::from(row.name.clone()),
+                    Cell::from(row.kind),
+                    Cell::from(format!("{:>5.1}", row.cpu_percent)),
+                    Cell::from(memory),
+                    Cell::from(rate(row.io_read_rate)),
+                    Cell::from(rate(row.io_write_rate)),
+                    Cell::from(row.pids.to_string()),
+                    Cell::from(row.path.clone()),
+                ])
+                .style(style)
+            })
+            .collect();
+
+        Table::new(rows)
+            .header(header)
+            .block(
+                Block::default()
+                    .title(Span::styled(
+                        self.title(),
+                        Style::default()
+                            .fg(Color::LightBlue)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightBlue)),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .widths(&WIDTHS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysfs;
+    use std::time::Duration;
+
+    fn container(name: &str) -> PathBuf {
+        sysfs::fixture(
+            name,
+            &[
+                ("fs/cgroup/cgroup.controllers", "cpu memory io pids\n"),
+                ("fs/cgroup/ci.slice/memory.max", "max\n"),
+                ("fs/cgroup/ci.slice/job-42.scope/memory.max", "8589934592\n"),
+                (
+                    "fs/cgroup/ci.slice/job-42.scope/memory.current",
+                    "3221225472\n",
+                ),
+                (
+                    "fs/cgroup/ci.slice/job-42.scope/memory.stat",
+                    "anon 2147483648\nfile 1073741824\ninactive_file 1073741824\n",
+                ),
+                ("fs/cgroup/ci.slice/job-42.scope/memory.swap.max", "0\n"),
+                ("fs/cgroup/ci.slice/job-42.scope/cpu.max", "200000 100000\n"),
+                (
+                    "fs/cgroup/ci.slice/job-42.scope/cpu.stat",
+                    "usage_usec 1000000\nnr_periods 100\nnr_throttled 10\nthrottled_usec 500000\n",
+                ),
+                ("fs/cgroup/ci.slice/job-42.scope/shell/memory.max", "max\n"),
+                (
+                    "fs/cgroup/ci.slice/job-42.scope/shell/memory.current",
+                    "1048576\n",
+                ),
+                (
+                    "fs/cgroup/ci.slice/job-42.scope/shell/cpu.stat",
+                    "usage_usec 7\n",
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn auto_uses_the_cgroup_that_sets_the_limits() {
+        let root = container("cgroup-auto");
+        let mut tracker = CgroupTracker::with_own_cgroup(
+            &CgroupMode::Auto,
+            &root.join("fs/cgroup"),
+            "0::/ci.slice/job-42.scope/shell\n",
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        let stats = tracker.update_at(256, start).unwrap();
+        assert_eq!(stats.path, "/ci.slice/job-42.scope/shell");
+        assert_eq!(stats.memory_max, Some(8 << 30));
+        // 3 GiB menos 1 GiB de cache inativo
+        assert_eq!(stats.memory_used, Some(2 << 30));
+        assert_eq!(stats.swap_max, Some(0));
+        assert_eq!(stats.cpu_quota, Some(2.0));
+        assert_eq!(stats.cpu_percent, None);
+
+        // 1.5 s de CPU em 1 s com cota de 2 núcleos; 20 de 50 períodos estrangulados
+        sysfs::write_fixture(
+            &root,
+            "fs/cgroup/ci.slice/job-42.scope/cpu.stat",
+            "usage_usec 2500000\nnr_periods 150\nnr_throttled 30\nthrottled_usec 900000\n",
+        );
+        let stats = tracker
+            .update_at(256, start + Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(stats.cpu_percent, Some(75.0));
+        assert_eq!(stats.throttled_percent, 40.0);
+        assert_eq!(stats.throttled_periods, 30);
+        assert_eq!(stats.throttled_seconds, 0.9);
+    }
+
+    #[test]
+    fn explicit_path_and_unlimited_auto() {
+        let root = container("cgroup-path");
+        let cgroups = root.join("fs/cgroup");
+
+        // Sem limites na cadeia, o modo auto não interfere
+        let mut tracker =
+            CgroupTracker::with_own_cgroup(&CgroupMode::Auto, &cgroups, "0::/ci.slice\n").unwrap();
+        assert_eq!(tracker.update(4), None);
+
+        // Com um caminho explícito, o uso é do próprio cgroup
+        let mut tracker = CgroupTracker::with_own_cgroup(
+            &parse_mode("ci.slice/job-42.scope/shell").unwrap(),
+            &cgroups,
+            "",
+        )
+        .unwrap();
+        let stats = tracker.update(4).unwrap();
+        assert_eq!(stats.memory_used, Some(1 << 20));
+        assert_eq!(stats.memory_max, Some(8 << 30));
+
+        assert!(CgroupTracker::with_own_cgroup(&CgroupMode::Off, &cgroups, "").is_none());
+        // cgroup v1 (sem cgroup.controllers) não é suportado
+        assert!(CgroupTracker::with_own_cgroup(&CgroupMode::Auto, &root, "").is_none());
+    }
+
+    #[test]
+    fn names_containers_and_pods() {
+        let docker = "3f4e5d6c7b8a9f0e1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e";
+        assert_eq!(
+            describe(&format!("docker-{}.scope", docker), "/system.slice"),
+            ("docker:3f4e5d6c7b8a".to_string(), "docker")
+        );
+        assert_eq!(
+            describe(&format!("libpod-{}.scope", docker), "/machine.slice").1,
+            "podman"
+        );
+        assert_eq!(describe(docker, "/docker").0, "docker:3f4e5d6c7b8a");
+        assert_eq!(
+            describe(
+                "kubepods-burstable-pod0c8e1f2a_55b1_4c1e_9d8e_123456789abc.slice",
+                "/kubepods.slice/kubepods-burstable.slice"
+            ),
+            ("pod:0c8e1f2a".to_string(), "pod")
+        );
+        assert_eq!(
+            describe(
+                "pod0c8e1f2a-55b1-4c1e-9d8e-123456789abc",
+                "/kubepods/burstable"
+            )
+            .0,
+            "pod:0c8e1f2a"
+        );
+        assert_eq!(describe("podman.service", "/system.slice").1, "service");
+        assert_eq!(
+            describe("kubepods-burstable.slice", "/kubepods.slice"),
+            ("kubepods-burstable.slice".to_string(), "slice")
+        );
+        assert_eq!(describe("nginx.service", "/system.slice").1, "service");
+        assert_eq!(describe("docker-compose.scope", "/user.slice").1, "scope");
+        assert_eq!(
+            parse_io_stat(
+                "8:0 rbytes=1024 wbytes=4096 rios=1 wios=2\n259:0 rbytes=1024 wbytes=0\n"
+            ),
+            (2048, 4096)
+        );
+    }
+
+    #[test]
+    fn scans_the_cgroup_tree() {
+        let pod = "kubepods.slice/kubepods-besteffort.slice/\
+                   kubepods-besteffort-pod0c8e1f2a_55b1.slice";
+        let root = sysfs::fixture(
+            "cgroup-scan",
+            &[
+                ("fs/cgroup/cgroup.controllers", "cpu memory io pids\n"),
+                ("fs/cgroup/cpu.stat", "usage_usec 999999999\n"),
+                ("fs/cgroup/system.slice/cpu.stat", "usage_usec 5000000\n"),
+                (
+                    "fs/cgroup/system.slice/docker-3f4e5d6c7b8a9f0e1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e.scope/cpu.stat",
+                    "usage_usec 1000000\nnr_periods 0\n",
+                ),
+                (
+                    "fs/cgroup/system.slice/docker-3f4e5d6c7b8a9f0e1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e.scope/memory.current",
+                    "104857600\n",
+                ),
+                (
+                    "fs/cgroup/system.slice/docker-3f4e5d6c7b8a9f0e1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e.scope/memory.max",
+                    "536870912\n",
+                ),
+                (
+                    "fs/cgroup/system.slice/docker-3f4e5d6c7b8a9f0e1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e.scope/io.stat",
+                    "8:0 rbytes=0 wbytes=0\n",
+                ),
+                (
+                    "fs/cgroup/system.slice/docker-3f4e5d6c7b8a9f0e1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e.scope/pids.current",
+                    "12\n",
+                ),
+                (&format!("fs/cgroup/{}/cpu.stat", pod), "usage_usec 0\n"),
+                (
+                    &format!("fs/cgroup/{}/cri-containerd-9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b.scope/cpu.stat", pod),
+                    "usage_usec 0\n",
+                ),
+            ],
+        );
+
+        let mut scanner = CgroupScanner::new(&root);
+        let start = Instant::now();
+        let first = scanner.scan_at(start);
+        let names: Vec<&str> = first.iter().map(|cgroup| cgroup.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "kubepods.slice",
+                "kubepods-besteffort.slice",
+                "pod:0c8e1f2a",
+                "containerd:9a8b7c6d5e4f",
+                "system.slice",
+                "docker:3f4e5d6c7b8a",
+            ]
+        );
+        let docker = &first[5];
+        assert_eq!(docker.path, "/system.slice/docker-3f4e5d6c7b8a9f0e1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e.scope");
+        assert_eq!(docker.memory_max, Some(512 << 20));
+        assert_eq!(docker.pids, 12);
+        assert_eq!(docker.cpu_percent, 0.0);
+
+        // 3 s de CPU em 2 s (1,5 núcleo) e 4 MiB escritos
+        let dir = "fs/cgroup/system.slice/docker-3f4e5d6c7b8a9f0e1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e.scope";
+        sysfs::write_fixture(&root, &format!("{}/cpu.stat", dir), "usage_usec 4000000\n");
+        sysfs::write_fixture(
+            &root,
+            &format!("{}/io.stat", dir),
+            "8:0 rbytes=0 wbytes=4194304\n",
+        );
+        let second = scanner.scan_at(start + Duration::from_secs(2));
+        let docker = &second[5];
+        assert_eq!(docker.cpu_percent, 150.0);
+        assert_eq!(docker.io_write_rate, 2097152.0);
+        assert_eq!(second[4].cpu_percent, 0.0);
+    }
+}
@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::sysfs::{self, read_number, read_string};
+
+/// Frequência de um núcleo lógico, de `cpuN/cpufreq`; valores em MHz.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoreFrequency {
+    pub cpu: u32,
+    pub current_mhz: f64,
+    pub min_mhz: Option<f64>,
+    pub max_mhz: Option<f64>,
+    pub governor: Option<String>,
+}
+
+/// Lê `<root>/devices/system/cpu/cpuN/cpufreq` de cada núcleo (o kernel informa em kHz).
+pub fn read_frequencies(sysfs_root: &Path) -> Vec<CoreFrequency> {
+    sysfs::numbered_entries(&sysfs_root.join("devices/system/cpu"), "cpu")
+        .into_iter()
+        .filter_map(|(cpu, path)| {
+            let cpufreq = path.join("cpufreq");
+            let mhz = |name: &str| read_number(&cpufreq.join(name)).map(|khz| khz as f64 / 1000.0);
+            Some(CoreFrequency {
+                cpu,
+                current_mhz: mhz("scaling_cur_freq")?,
+                min_mhz: mhz("scaling_min_freq").or_else(|| mhz("cpuinfo_min_freq")),
+                max_mhz: mhz("scaling_max_freq").or_else(|| mhz("cpuinfo_max_freq")),
+                governor: read_string(&cpufreq.join("scaling_governor")),
+            })
+        })
+        .collect()
+}
+
+/// Soma dos contadores `thermal_throttle` de todos os núcleos (só Intel).
+pub fn read_throttle_count(sysfs_root: &Path) -> Option<u64> {
+    let counts: Vec<u64> = sysfs::numbered_entries(&sysfs_root.join("devices/system/cpu"), "cpu")
+        .into_iter()
+        .flat_map(|(_, path)| {
+            let throttle = path.join("thermal_throttle");
+            [
+                read_number(&throttle.join("core_throttle_count")),
+                read_number(&throttle.join("package_throttle_count")),
+            ]
+        })
+        .flatten()
+        .collect();
+    (!counts.is_empty()).then(|| counts.iter().sum())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleReason {
+    /// Os contadores `thermal_throttle` aumentaram
+    Thermal,
+    /// Clock bem abaixo do máximo com a CPU carregada
+    LowClocks,
+}
+
+impl fmt::Display for ThrottleReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThrottleReason::Thermal => write!(f, "thermal"),
+            ThrottleReason::LowClocks => write!(f, "low clocks"),
+        }
+    }
+}
+
+// Quanto tempo um evento térmico continua marcado
+const THERMAL_HOLD: Duration = Duration::from_secs(10);
+// Carga e fração do clock máximo que contam como "lento sob carga", e por quanto tempo
+const BUSY_PERCENT: f64 = 80.0;
+const LOW_CLOCK_RATIO: f64 = 0.75;
+const LOW_CLOCK_AFTER: Duration = Duration::from_secs(5);
+
+/// Detecta throttling pelos contadores térmicos e por clocks baixos sustentados sob carga.
+#[derive(Default)]
+pub struct ThrottleDetector {
+    last_count: Option<u64>,
+    thermal_since: Option<Instant>,
+    low_since: Option<Instant>,
+}
+
+impl ThrottleDetector {
+    pub fn update(
+        &mut self,
+        now: Instant,
+        count: Option<u64>,
+        frequencies: &[CoreFrequency],
+        usage: f64,
+    ) -> Option<ThrottleReason> {
+        if let (Some(last), Some(count)) = (self.last_count, count) {
+            if count > last {
+                self.thermal_since = Some(now);
+            }
+        }
+        self.last_count = count;
+
+        let (current, max) = frequencies
+            .iter()
+            .filter_map(|core| Some((core.current_mhz, core.max_mhz?)))
+            .fold((0.0, 0.0), |(current, max), (c, m)| (current + c, max + m));
+        let slow = max > 0.0 && usage >= BUSY_PERCENT && current / max < LOW_CLOCK_RATIO;
+        self.low_since = match (slow, self.low_since) {
+            (false, _) => None,
+            (true, since) => since.or(Some(now)),
+        };
+
+        if self
+            .thermal_since
+            .is_some_and(|since| now.duration_since(since) < THERMAL_HOLD)
+        {
+            Some(ThrottleReason::Thermal)
+        } else if self
+            .low_since
+            .is_some_and(|since| now.duration_since(since) >= LOW_CLOCK_AFTER)
+        {
+            Some(ThrottleReason::LowClocks)
+        } else {
+            None
+        }
+    }
+}
+
+/// `3.21 GHz` ou `800 MHz`
+pub fn format_mhz(mhz: f64) -> String {
+    if mhz >= 1000.0 {
+        format!("{:.2} GHz", mhz / 1000.0)
+    } else {
+        format!("{:.0} MHz", mhz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_cpufreq_and_throttle_counters() {
+        let root = sysfs::fixture(
+            "cpufreq",
+            &[
+                (
+                    "devices/system/cpu/cpu0/cpufreq/scaling_cur_freq",
+                    "3200000\n",
+                ),
+                (
+                    "devices/system/cpu/cpu0/cpufreq/scaling_min_freq",
+                    "800000\n",
+                ),
+                (
+                    "devices/system/cpu/cpu0/cpufreq/scaling_max_freq",
+                    "4700000\n",
+                ),
+                (
+                    "devices/system/cpu/cpu0/cpufreq/scaling_governor",
+                    "powersave\n",
+                ),
+                (
+                    "devices/system/cpu/cpu0/thermal_throttle/core_throttle_count",
+                    "3\n",
+                ),
+                (
+                    "devices/system/cpu/cpu0/thermal_throttle/package_throttle_count",
+                    "7\n",
+                ),
+                (
+                    "devices/system/cpu/cpu1/cpufreq/scaling_cur_freq",
+                    "800000\n",
+                ),
+                (
+                    "devices/system/cpu/cpu1/cpufreq/cpuinfo_max_freq",
+                    "4700000\n",
+                ),
+                (
+                    "devices/system/cpu/cpu1/thermal_throttle/core_throttle_count",
+                    "1\n",
+                ),
+                // Não são núcleos
+                ("devices/system/cpu/cpufreq/boost", "1\n"),
+                ("devices/system/cpu/cpuidle/current_driver", "intel_idle\n"),
+            ],
+        );
+
+        let frequencies = read_frequencies(&root);
+        assert_eq!(frequencies.len(), 2);
+        assert_eq!(frequencies[0].current_mhz, 3200.0);
+        assert_eq!(frequencies[0].min_mhz, Some(800.0));
+        assert_eq!(frequencies[0].governor.as_deref(), Some("powersave"));
+        assert_eq!(frequencies[1].cpu, 1);
+        assert_eq!(frequencies[1].max_mhz, Some(4700.0));
+        assert_eq!(frequencies[1].governor, None);
+        assert_eq!(read_throttle_count(&root), Some(11));
+    }
+
+    #[test]
+    fn detects_thermal_events_and_sustained_low_clocks() {
+        let core = |current_mhz: f64| CoreFrequency {
+            current_mhz,
+            max_mhz: Some(4000.0),
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let at = |seconds: u64| start + Duration::from_secs(seconds);
+
+        let mut detector = ThrottleDetector::default();
+        assert_eq!(detector.update(at(0), Some(5), &[core(3900.0)], 95.0), None);
+        assert_eq!(
+            detector.update(at(1), Some(6), &[core(3900.0)], 95.0),
+            Some(ThrottleReason::Thermal)
+        );
+        assert_eq!(
+            detector.update(at(12), Some(6), &[core(3900.0)], 95.0),
+            None
+        );
+
+        // Lento, mas ocioso: normal
+        assert_eq!(detector.update(at(13), Some(6), &[core(800.0)], 5.0), None);
+        // Lento sob carga só conta depois de alguns segundos
+        assert_eq!(
+            detector.update(at(14), Some(6), &[core(2000.0)], 95.0),
+            None
+        );
+        assert_eq!(
+            detector.update(at(19), Some(6), &[core(2000.0)], 95.0),
+            Some(ThrottleReason::LowClocks)
+        );
+        assert_eq!(
+            detector.update(at(20), Some(6), &[core(3900.0)], 95.0),
+            None
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use humansize::{format_size, BINARY};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+use sysinfo::{DiskExt, System, SystemExt};
+use tui::{
+    layout::Constraint,
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Row, Table},
+};
+
+// /proc/diskstats sempre conta setores de 512 bytes, independente do dispositivo
+const SECTOR_SIZE: u64 = 512;
+
+#[derive(Clone)]
+pub struct DiskUsage {
+    pub mount_point: String,
+    pub fs_type: String,
+    pub used: u64,
+    pub total: u64,
+}
+
+impl DiskUsage {
+    pub fn percent(&self) -> f64 {
+        if self.total > 0 {
+            (self.used as f64 / self.total as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+pub fn read_disks(sys: &System) -> Vec<DiskUsage> {
+    sys.disks()
+        .iter()
+        .map(|disk| DiskUsage {
+            mount_point: disk.mount_point().to_string_lossy().to_string(),
+            fs_type: String::from_utf8_lossy(disk.file_system()).to_string(),
+            used: disk.total_space().saturating_sub(disk.available_space()),
+            total: disk.total_space(),
+        })
+        .collect()
+}
+
+/// Taxa de leitura/escrita de um dispositivo de bloco, em bytes por segundo.
+#[derive(Clone)]
+pub struct DiskIo {
+    pub name: String,
+    pub read_rate: f64,
+    pub write_rate: f64,
+}
+
+/// Guarda os contadores anteriores de /proc/diskstats para calcular taxas.
+#[derive(Default)]
+pub struct DiskIoTracker {
+    previous: HashMap<String, (u64, u64)>,
+    last: Option<Instant>,
+}
+
+impl DiskIoTracker {
+    #[cfg(target_os = "linux")]
+    pub fn update(&mut self, sysfs_root: &Path) -> Vec<DiskIo> {
+        let contents = std::fs::read_to_string("/proc/diskstats").unwrap_or_default();
+        self.update_from(whole_disks(&contents, sysfs_root), Instant::now())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn update(&mut self, _sysfs_root: &Path) -> Vec<DiskIo> {
+        Vec::new()
+    }
+
+    fn update_from(&mut self, devices: Vec<(String, u64, u64)>, now: Instant) -> Vec<DiskIo> {
+        let elapsed = self
+            .last
+            .map(|last| now.duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+        self.last = Some(now);
+
+        let mut rates = Vec::new();
+        let mut current = HashMap::new();
+        for (name, read, written) in devices {
+            let (read_rate, write_rate) = match self.previous.get(&name) {
+                Some(&(prev_read, prev_written)) if elapsed > 0.0 => (
+                    read.saturating_sub(prev_read) as f64 / elapsed,
+                    written.saturating_sub(prev_written) as f64 / elapsed,
+                ),
+                _ => (0.0, 0.0),
+            };
+            rates.push(DiskIo {
+                name: name.clone(),
+                read_rate,
+                write_rate,
+            });
+            current.insert(name, (read, written));
+        }
+        self.previous = current;
+        rates
+    }
+}
+
+/// Extrai (dispositivo, bytes lidos, bytes escritos) de cada linha de /proc/diskstats.
+pub fn parse_diskstats(contents: &str) -> Vec<(String, u64, u64)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+            let sectors_read: u64 = fields[5].parse().ok()?;
+            let sectors_written: u64 = fields[9].parse().ok()?;
+            Some((
+                fields[2].to_string(),
+                sectors_read * SECTOR_SIZE,
+                sectors_written * SECTOR_SIZE,
+            ))
+        })
+        .collect()
+}
+
+/// Contadores só dos discos inteiros (partições não aparecem em `<sysfs>/block`).
+fn whole_disks(contents: &str, sysfs_root: &Path) -> Vec<(String, u64, u64)> {
+    parse_diskstats(contents)
+        .into_iter()
+        .filter(|(name, _, _)| {
+            !name.starts_with("loop")
+                && !name.starts_with("ram")
+                && sysfs_root.join("block").join(name).exists()
+        })
+        .collect()
+}
+
+pub fn draw_disks(disks: &[DiskUsage], alerting: bool) -> Table<'static> {
+    let border = if alerting {
+        Color::Red
+    } else {
+        Color::LightGreen
+    };
+
+    let header = Row::new(vec!["Mount", "FS", "Used", "Total", "Use%"])
+        .style(Style::default().fg(Color::LightGreen));
+
+    let rows: Vec<Row> = disks
+        .iter()
+        .map(|disk| {
+            let percent = disk.percent();
+            let color = if percent >= 90.0 {
+                Color::Red
+            } else if percent >= 75.0 {
+                Color::Yellow
+            } else {
+                Color::LightGreen
+            };
+            Row::new(vec![
+                Cell::from(disk.mount_point.clone()),
+                Cell::from(disk.fs_type.clone()),
+                Cell::from(format_size(disk.used, BINARY)),
+                Cell::from(format_size(disk.total, BINARY)),
+                Cell::from(format!("{:>5.1}%", percent)).style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    "Disks",
+                    Style::default().fg(border).add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border)),
+        )
+        .widths(&[
+            Constraint::Min(12),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(7),
+        ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysfs;
+
+    const DISKSTATS: &str = "\
+   7       0 loop0 120 0 2400 10 0 0 0 0 0 20 10 0 0 0 0
+   8       0 sda 1000 20 2048 300 500 10 4096 200 0 400 500 0 0 0 0
+   8       1 sda1 900 20 1024 250 400 10 2048 150 0 300 400 0 0 0 0
+ 259       0 nvme0n1 10 0 8 1 20 0 16 2 0 3 3 0 0 0 0
+   1       0 ram0 0 0 0 0 0 0 0 0 0 0 0
+   8      16 sdb 1 2
+";
+
+    #[test]
+    fn parses_sectors_as_bytes() {
+        let devices = parse_diskstats(DISKSTATS);
+        let names: Vec<&str> = devices.iter().map(|(name, _, _)| name.as_str()).collect();
+        assert_eq!(names, ["loop0", "sda", "sda1", "nvme0n1", "ram0"]);
+        // Setores de 512 bytes, mesmo em discos com setores físicos de 4 KiB
+        assert_eq!(devices[1], ("sda".to_string(), 2048 * 512, 4096 * 512));
+        assert_eq!(devices[3], ("nvme0n1".to_string(), 4096, 8192));
+    }
+
+    #[test]
+    fn keeps_only_whole_disks() {
+        let root = sysfs::fixture(
+            "diskstats",
+            &[
+                ("block/sda/size", "1000"),
+                ("block/nvme0n1/size", "1000"),
+                ("block/loop0/size", "0"),
+                ("block/ram0/size", "0"),
+            ],
+        );
+        let names: Vec<String> = whole_disks(DISKSTATS, &root)
+            .into_iter()
+            .map(|(name, _, _)| name)
+            .collect();
+        assert_eq!(names, ["sda", "nvme0n1"]);
+    }
+}
@@ -0,0 +1,480 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Sparkline},
+    Frame,
+};
+
+use crate::format_rate;
+use crate::remote::RemoteClient;
+use crate::snapshot::Snapshot;
+
+// Mesmos limites do painel de discos: amarelo a partir de 75%, vermelho a partir de 90%
+const WARN_PERCENT: f64 = 75.0;
+const CRITICAL_PERCENT: f64 = 90.0;
+const NAME_WIDTH: u16 = 20;
+const STATUS_WIDTH: u16 = 12;
+
+/// Conteúdo do `hosts.toml`.
+#[derive(Deserialize)]
+struct HostsFile {
+    /// Token padrão para os hosts que não definem o seu
+    #[serde(default)]
+    token: Option<String>,
+    hosts: Vec<HostEntry>,
+}
+
+/// Um host: só o endereço, ou uma tabela com nome e token próprios.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HostEntry {
+    Address(String),
+    Detailed {
+        address: String,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        token: Option<String>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+struct HostSpec {
+    name: String,
+    address: String,
+    token: Option<String>,
+}
+
+/// Lê a lista de hosts; o token do host vence o `--token`, que vence o do arquivo.
+fn parse_hosts(contents: &str, token: Option<&str>) -> Result<Vec<HostSpec>, Box<dyn Error>> {
+    let file: HostsFile = toml::from_str(contents)?;
+    let default_token = token.map(str::to_string).or(file.token);
+    Ok(file
+        .hosts
+        .into_iter()
+        .map(|entry| {
+            let (address, name, token) = match entry {
+                HostEntry::Address(address) => (address, None, None),
+                HostEntry::Detailed {
+                    address,
+                    name,
+                    token,
+                } => (address, name, token),
+            };
+            // Sem nome, o endereço sem a porta
+            let name = name.unwrap_or_else(|| {
+                address
+                    .rsplit_once(':')
+                    .map_or(address.as_str(), |(host, _)| host)
+                    .to_string()
+            });
+            HostSpec {
+                name,
+                address,
+                token: token.or_else(|| default_token.clone()),
+            }
+        })
+        .collect())
+}
+
+/// Colunas da visão geral, cada uma com um valor por amostra.
+#[derive(Clone, Copy)]
+enum Column {
+    Cpu,
+    Memory,
+    Swap,
+    Gpu,
+    Network,
+}
+
+impl Column {
+    const ALL: [Column; 5] = [
+        Column::Cpu,
+        Column::Memory,
+        Column::Swap,
+        Column::Gpu,
+        Column::Network,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Column::Cpu => "CPU",
+            Column::Memory => "Memory",
+            Column::Swap => "Swap",
+            Column::Gpu => "GPU",
+            Column::Network => "Network",
+        }
+    }
+
+    /// Percentual (ou bytes/s na rede); `None` quando o host não tem o recurso.
+    fn value(self, sample: &Snapshot) -> Option<f64> {
+        match self {
+            Column::Cpu => Some(sample.cpu.usage),
+            Column::Memory => Some(sample.memory.percent),
+            Column::Swap => (sample.swap.total > 0).then_some(sample.swap.percent),
+            Column::Gpu => sample.gpus.iter().map(|gpu| gpu.usage).reduce(f64::max),
+            Column::Network => sample
+                .network
+                .as_ref()
+                .map(|network| network.rx_rate + network.tx_rate),
+        }
+    }
+
+    fn is_percent(self) -> bool {
+        !matches!(self, Column::Network)
+    }
+
+    fn label_width(self) -> u16 {
+        if self.is_percent() {
+            6
+        } else {
+            12
+        }
+    }
+}
+
+/// O recurso mais ocupado da amostra (CPU, memória, swap ou GPU).
+fn load(sample: &Snapshot) -> f64 {
+    Column::ALL
+        .iter()
+        .filter(|column| column.is_percent())
+        .filter_map(|column| column.value(sample))
+        .fold(0.0, f64::max)
+}
+
+fn health_color(percent: f64) -> Color {
+    if percent >= CRITICAL_PERCENT {
+        Color::Red
+    } else if percent >= WARN_PERCENT {
+        Color::Yellow
+    } else {
+        Color::LightGreen
+    }
+}
+
+pub struct Host {
+    pub name: String,
+    pub client: RemoteClient,
+}
+
+impl Host {
+    fn latest(&self) -> Option<&Snapshot> {
+        self.client.recent().back()
+    }
+
+    fn load(&self) -> Option<f64> {
+        self.latest().map(load)
+    }
+
+    /// Estado da conexão, com a cor da coluna de status.
+    fn status(&self) -> (String, Color) {
+        match self.client.sample_age() {
+            Some(_) if !self.client.is_stale() => ("live".to_string(), Color::LightGreen),
+            Some(age) => (format!("stale {}s", age.as_secs()), Color::Yellow),
+            None if self.client.is_connected() => ("waiting".to_string(), Color::DarkGray),
+            None => ("down".to_string(), Color::Red),
+        }
+    }
+}
+
+/// `monitor fleet`: uma linha por agente, com sparklines coloridas pela carga.
+pub struct Fleet {
+    pub hosts: Vec<Host>,
+    // Índice em `hosts` do host selecionado e a ordem de exibição
+    selected: usize,
+    order: Vec<usize>,
+    // Primeira linha visível, para rolar listas maiores que o terminal
+    offset: usize,
+    by_load: bool,
+}
+
+impl Fleet {
+    /// Lê o arquivo de hosts e começa a conectar em todos eles.
+    pub fn load(path: &Path, token: Option<&str>, history: usize) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let specs =
+            parse_hosts(&contents, token).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if specs.is_empty() {
+            return Err(format!("{} has no hosts", path.display()).into());
+        }
+        let hosts: Vec<Host> = specs
+            .into_iter()
+            .map(|spec| Host {
+                name: spec.name,
+                client: RemoteClient::start(spec.address, spec.token, history),
+            })
+            .collect();
+        Ok(Self {
+            order: (0..hosts.len()).collect(),
+            hosts,
+            selected: 0,
+            offset: 0,
+            by_load: false,
+        })
+    }
+
+    /// Recebe as amostras de todos os hosts e reordena a lista.
+    pub fn poll(&mut self) {
+        for host in &mut self.hosts {
+            host.client.poll();
+        }
+        self.order = (0..self.hosts.len()).collect();
+        if self.by_load {
+            // Hosts sem amostra vão para o fim
+            let hosts = &self.hosts;
+            self.order.sort_by(|a, b| {
+                let (a, b) = (hosts[*a].load(), hosts[*b].load());
+                b.unwrap_or(-1.0).total_cmp(&a.unwrap_or(-1.0))
+            });
+        }
+    }
+
+    pub fn selected_mut(&mut self) -> Option<&mut Host> {
+        self.hosts.get_mut(self.selected)
+    }
+
+    fn position(&self) -> usize {
+        self.order
+            .iter()
+            .position(|&index| index == self.selected)
+            .unwrap_or(0)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let last = self.order.len().saturating_sub(1) as isize;
+        let position = (self.position() as isize + delta).clamp(0, last) as usize;
+        if let Some(&index) = self.order.get(position) {
+            self.selected = index;
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Home => self.move_selection(isize::MIN / 2),
+            KeyCode::End => self.move_selection(isize::MAX / 2),
+            KeyCode::Char('s') => {
+                self.by_load = !self.by_load;
+                self.poll();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn title(&self) -> String {
+        let saturated = self
+            .hosts
+            .iter()
+            .filter(|host| !host.client.is_stale())
+            .filter(|host| host.load().is_some_and(|load| load >= CRITICAL_PERCENT))
+            .count();
+        let stale = self
+            .hosts
+            .iter()
+            .filter(|host| host.client.is_stale())
+            .count();
+        format!(
+            "Fleet ({} hosts, {} saturated, {} stale) - order: {}",
+            self.hosts.len(),
+            saturated,
+            stale,
+            if self.by_load { "load" } else { "file" }
+        )
+    }
+
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect) {
+        let block = Block::default()
+            .title(self.title())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightBlue));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        if inner.height < 2 {
+            return;
+        }
+
+        // Manter o host selecionado visível
+        let rows = (inner.height - 1) as usize;
+        let position = self.position();
+        if position < self.offset {
+            self.offset = position;
+        } else if position >= self.offset + rows {
+            self.offset = position + 1 - rows;
+        }
+
+        let header = Rect { height: 1, ..inner };
+        let titles = ["Host", "Status"]
+            .into_iter()
+            .chain(Column::ALL.iter().map(|column| column.title()));
+        for (title, cell) in titles.zip(row_cells(header)) {
+            f.render_widget(
+                Paragraph::new(title).style(
+                    Style::default()
+                        .fg(Color::LightBlue)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                cell,
+            );
+        }
+
+        for (line, &index) in self.order.iter().skip(self.offset).take(rows).enumerate() {
+            let area = Rect {
+                y: inner.y + 1 + line as u16,
+                height: 1,
+                ..inner
+            };
+            draw_host(f, &self.hosts[index], index == self.selected, area);
+        }
+    }
+}
+
+/// Divide uma linha em nome, status e uma célula por coluna.
+fn row_cells(area: Rect) -> Vec<Rect> {
+    let mut cells = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(NAME_WIDTH),
+            Constraint::Length(STATUS_WIDTH),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    // As proporções valem sobre o espaço que sobra, não sobre a linha inteira
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            Column::ALL
+                .iter()
+                .map(|_| Constraint::Ratio(1, Column::ALL.len() as u32))
+                .collect::<Vec<_>>(),
+        )
+        .split(cells[2]);
+    cells.truncate(2);
+    cells.extend(columns);
+    cells
+}
+
+fn draw_host<B: Backend>(f: &mut Frame<B>, host: &Host, selected: bool, area: Rect) {
+    let cells = row_cells(area);
+    let stale = host.client.is_stale();
+    let dim = |color: Color| if stale { Color::DarkGray } else { color };
+
+    let name_color = dim(host.load().map_or(Color::DarkGray, health_color));
+    let mut name_style = Style::default().fg(name_color);
+    if selected {
+        name_style = name_style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+    }
+    f.render_widget(
+        Paragraph::new(Span::styled(host.name.clone(), name_style)),
+        cells[0],
+    );
+    let (status, status_color) = host.status();
+    f.render_widget(
+        Paragraph::new(Span::styled(status, Style::default().fg(status_color))),
+        cells[1],
+    );
+
+    let samples = host.client.recent();
+    for (column, &cell) in Column::ALL.iter().zip(&cells[2..]) {
+        let Some(current) = host.latest().and_then(|sample| column.value(sample)) else {
+            f.render_widget(
+                Paragraph::new(Span::styled("-", Style::default().fg(Color::DarkGray))),
+                cell,
+            );
+            continue;
+        };
+        let (label, color) = if column.is_percent() {
+            (format!("{:>4.0}%", current), dim(health_color(current)))
+        } else {
+            (format_rate(current), dim(Color::Cyan))
+        };
+        let parts = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(column.label_width()), Constraint::Min(0)])
+            .split(cell);
+        f.render_widget(
+            Paragraph::new(Spans::from(Span::styled(label, Style::default().fg(color)))),
+            parts[0],
+        );
+
+        // A sparkline desenha as primeiras amostras que cabem; passar só as mais recentes
+        let values: Vec<u64> = samples
+            .iter()
+            .map(|sample| column.value(sample).unwrap_or(0.0).max(0.0) as u64)
+            .collect();
+        let width = parts[1].width.saturating_sub(1) as usize;
+        let mut sparkline = Sparkline::default()
+            .data(&values[values.len().saturating_sub(width)..])
+            .style(Style::default().fg(color));
+        if column.is_percent() {
+            sparkline = sparkline.max(100);
+        }
+        f.render_widget(sparkline, parts[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_addresses_and_detailed_hosts() {
+        let contents = r#"
+            token = "file"
+            hosts = [
+                "build01:7070",
+                { address = "10.0.0.2:7070", name = "gpu-box", token = "own" },
+                { address = "build03:7070" },
+            ]
+        "#;
+        let hosts = parse_hosts(contents, None).unwrap();
+        assert_eq!(
+            hosts[0],
+            HostSpec {
+                name: "build01".to_string(),
+                address: "build01:7070".to_string(),
+                token: Some("file".to_string()),
+            }
+        );
+        assert_eq!(hosts[1].name, "gpu-box");
+        assert_eq!(hosts[1].token.as_deref(), Some("own"));
+        assert_eq!(hosts[2].name, "build03");
+
+        // --token substitui o padrão do arquivo, mas não o token de cada host
+        let hosts = parse_hosts(contents, Some("cli")).unwrap();
+        assert_eq!(hosts[0].token.as_deref(), Some("cli"));
+        assert_eq!(hosts[1].token.as_deref(), Some("own"));
+
+        assert!(parse_hosts("hosts = [1]", None).is_err());
+    }
+
+    #[test]
+    fn load_is_the_busiest_resource() {
+        let sample: Snapshot = serde_json::from_str(
+            r#"{"timestamp": "2024-01-01T00:00:00+00:00", "timestamp_ms": 0,
+                "cpu": {"usage": 40.0, "cores": []},
+                "memory": {"total": 100, "used": 80, "percent": 80.0},
+                "swap": {"total": 0, "used": 0, "percent": 0.0},
+                "network": {"rx_rate": 5000.0, "tx_rate": 0.0, "rx_total": 0, "tx_total": 0,
+                            "interfaces": []}}"#,
+        )
+        .unwrap();
+        assert_eq!(Column::Swap.value(&sample), None);
+        assert_eq!(Column::Gpu.value(&sample), None);
+        // A rede não é percentual e fica fora da carga
+        assert_eq!(load(&sample), 80.0);
+        assert_eq!(health_color(load(&sample)), Color::Yellow);
+        assert_eq!(health_color(95.0), Color::Red);
+    }
+}
@@ -0,0 +1,421 @@
+use clap::ValueEnum;
+use nvml_wrapper::{enum_wrappers::device::TemperatureSensor, Nvml};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::snapshot;
+use crate::sysfs::{self, read_number, read_string};
+
+/// Leitura de uma GPU; memória em bytes e temperatura em °C.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GpuStats {
+    pub model: String,
+    pub usage: f64,
+    pub memory_percent: f64,
+    #[serde(default)]
+    pub memory_used: u64,
+    #[serde(default)]
+    pub memory_total: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Clock atual e máximo em MHz, quando o driver informa
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_mhz: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_frequency_mhz: Option<f64>,
+    /// Consumo da placa em W (NVML)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_watts: Option<f64>,
+}
+
+impl GpuStats {
+    fn with_memory(mut self, used: u64, total: u64) -> Self {
+        self.memory_used = used;
+        self.memory_total = total;
+        self.memory_percent = snapshot::percent(used, total);
+        self
+    }
+}
+
+/// Uma fonte de métricas de GPU que enumera todos os dispositivos que conhece.
+pub trait GpuBackend {
+    /// Lê todos os dispositivos; um dispositivo com erro entra com os valores zerados.
+    fn sample(&mut self) -> Vec<GpuStats>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum BackendKind {
+    /// NVML e depois sysfs, usando todos os que encontrarem GPUs
+    Auto,
+    Nvml,
+    /// amdgpu via /sys/class/drm (Linux)
+    Amd,
+    /// i915/xe via /sys/class/drm (Linux)
+    Intel,
+    /// Quatro GPUs simuladas, para testar sem hardware
+    Mock,
+}
+
+/// Cria os backends pedidos, descartando os que não têm nenhum dispositivo.
+///
+/// `sysfs_root` normalmente é `/sys`; os backends de sysfs leem `<root>/class/drm`.
+pub fn detect(kind: BackendKind, sysfs_root: &Path) -> Vec<Box<dyn GpuBackend>> {
+    let drm = sysfs_root.join("class/drm");
+    let mut backends: Vec<Box<dyn GpuBackend>> = Vec::new();
+    if matches!(kind, BackendKind::Auto | BackendKind::Nvml) && !cfg!(target_os = "macos") {
+        if let Some(backend) = NvmlBackend::new() {
+            backends.push(Box::new(backend));
+        }
+    }
+    if matches!(kind, BackendKind::Auto | BackendKind::Amd) {
+        let backend = AmdSysfsBackend::new(&drm);
+        if !backend.cards.is_empty() {
+            backends.push(Box::new(backend));
+        }
+    }
+    if matches!(kind, BackendKind::Auto | BackendKind::Intel) {
+        let backend = IntelSysfsBackend::new(&drm);
+        if !backend.cards.is_empty() {
+            backends.push(Box::new(backend));
+        }
+    }
+    if kind == BackendKind::Mock {
+        backends.push(Box::new(MockBackend::new(4)));
+    }
+    backends
+}
+
+pub struct NvmlBackend {
+    nvml: Nvml,
+}
+
+impl NvmlBackend {
+    pub fn new() -> Option<Self> {
+        let nvml = Nvml::init().ok()?;
+        (nvml.device_count().ok()? > 0).then_some(Self { nvml })
+    }
+}
+
+impl GpuBackend for NvmlBackend {
+    fn sample(&mut self) -> Vec<GpuStats> {
+        let count = self.nvml.device_count().unwrap_or(0);
+        (0..count)
+            .map(|index| {
+                let Ok(device) = self.nvml.device_by_index(index) else {
+                    return GpuStats::default();
+                };
+                let stats = GpuStats {
+                    model: device.name().unwrap_or_else(|_| "NVIDIA GPU".to_string()),
+                    usage: device
+                        .utilization_rates()
+                        .map_or(0.0, |rates| rates.gpu as f64),
+                    temperature: device
+                        .temperature(TemperatureSensor::Gpu)
+                        .ok()
+                        .map(|t| t as f64),
+                    // NVML informa em mW
+                    power_watts: device
+                        .power_usage()
+                        .ok()
+                        .map(|milliwatts| milliwatts as f64 / 1000.0),
+                    ..Default::default()
+                };
+                match device.memory_info() {
+                    Ok(memory) => stats.with_memory(memory.used, memory.total),
+                    Err(_) => stats,
+                }
+            })
+            .collect()
+    }
+}
+
+const AMD_VENDOR: &str = "0x1002";
+const INTEL_VENDOR: &str = "0x8086";
+
+/// GPUs AMD pelo driver amdgpu, lidas de `<drm>/cardN/device`.
+pub struct AmdSysfsBackend {
+    cards: Vec<PathBuf>,
+}
+
+impl AmdSysfsBackend {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            cards: find_cards(root, AMD_VENDOR),
+        }
+    }
+}
+
+impl GpuBackend for AmdSysfsBackend {
+    fn sample(&mut self) -> Vec<GpuStats> {
+        self.cards
+            .iter()
+            .map(|card| {
+                let device = card.join("device");
+                let stats = GpuStats {
+                    model: read_string(&device.join("product_name"))
+                        .unwrap_or_else(|| "AMD GPU".to_string()),
+                    usage: read_number(&device.join("gpu_busy_percent")).unwrap_or(0) as f64,
+                    // amdgpu expõe edge, junction e mem; edge é a temperatura "da GPU"
+                    temperature: hwmon_temperature(&device, "edge"),
+                    ..Default::default()
+                };
+                match (
+                    read_number(&device.join("mem_info_vram_used")),
+                    read_number(&device.join("mem_info_vram_total")),
+                ) {
+                    (Some(used), Some(total)) => stats.with_memory(used, total),
+                    _ => stats,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Temperatura do sensor `label` no hwmon do dispositivo, ou o primeiro sensor.
+fn hwmon_temperature(device: &Path, label: &str) -> Option<f64> {
+    let (_, hwmon) = sysfs::numbered_entries(&device.join("hwmon"), "hwmon")
+        .into_iter()
+        .next()?;
+    let temperatures = sysfs::hwmon_temperatures(&hwmon);
+    temperatures
+        .iter()
+        .find(|(name, _)| name == label)
+        .or(temperatures.first())
+        .map(|&(_, celsius)| celsius)
+}
+
+/// Contador de RC6 (ms em que a GPU ficou ociosa) de uma placa Intel.
+struct Rc6Sample {
+    residency_ms: u64,
+    at: Instant,
+}
+
+/// GPUs Intel pelos drivers i915 ou xe, lidas de `<drm>/cardN`.
+///
+/// Não há contador de uso: o uso é o tempo fora do RC6 entre duas leituras.
+pub struct IntelSysfsBackend {
+    cards: Vec<PathBuf>,
+    previous: HashMap<PathBuf, Rc6Sample>,
+}
+
+impl IntelSysfsBackend {
+    pub fn new(drm: &Path) -> Self {
+        Self {
+            cards: find_cards(drm, INTEL_VENDOR),
+            previous: HashMap::new(),
+        }
+    }
+
+    fn sample_at(&mut self, now: Instant) -> Vec<GpuStats> {
+        let mut devices = Vec::new();
+        for card in &self.cards {
+            let device = card.join("device");
+            let model = match read_string(&device.join("device")) {
+                Some(id) => format!("Intel GPU ({})", id),
+                None => "Intel GPU".to_string(),
+            };
+
+            // i915 expõe os arquivos em cardN; xe, por tile/gt em device/
+            let xe_gt = device.join("tile0/gt0");
+            let frequency = read_number(&card.join("gt_act_freq_mhz"))
+                .or_else(|| read_number(&xe_gt.join("freq0/act_freq")));
+            let max_frequency = read_number(&card.join("gt_max_freq_mhz"))
+                .or_else(|| read_number(&xe_gt.join("freq0/max_freq")));
+            let residency = read_number(&card.join("gt/gt0/rc6_residency_ms"))
+                .or_else(|| read_number(&card.join("power/rc6_residency_ms")))
+                .or_else(|| read_number(&xe_gt.join("gtidle/idle_residency_ms")));
+
+            let mut usage = 0.0;
+            if let Some(residency_ms) = residency {
+                if let Some(previous) = self.previous.get(card) {
+                    let elapsed_ms = now.duration_since(previous.at).as_secs_f64() * 1000.0;
+                    if elapsed_ms > 0.0 && residency_ms >= previous.residency_ms {
+                        let idle = (residency_ms - previous.residency_ms) as f64 / elapsed_ms;
+                        usage = ((1.0 - idle) * 100.0).clamp(0.0, 100.0);
+                    }
+                }
+                self.previous.insert(
+                    card.clone(),
+                    Rc6Sample {
+                        residency_ms,
+                        at: now,
+                    },
+                );
+            }
+
+            devices.push(GpuStats {
+                model,
+                usage,
+                temperature: hwmon_temperature(&device, "gpu"),
+                frequency_mhz: frequency.map(|f| f as f64),
+                max_frequency_mhz: max_frequency.map(|f| f as f64),
+                ..Default::default()
+            });
+        }
+        devices
+    }
+}
+
+impl GpuBackend for IntelSysfsBackend {
+    fn sample(&mut self) -> Vec<GpuStats> {
+        self.sample_at(Instant::now())
+    }
+}
+
+/// `cardN` (sem os conectores como `card0-DP-1`) cujo `device/vendor` é `vendor`.
+fn find_cards(drm: &Path, vendor: &str) -> Vec<PathBuf> {
+    sysfs::numbered_entries(drm, "card")
+        .into_iter()
+        .map(|(_, path)| path)
+        .filter(|path| read_string(&path.join("device/vendor")).as_deref() == Some(vendor))
+        .collect()
+}
+
+/// GPUs simuladas com carga variando suavemente.
+pub struct MockBackend {
+    count: usize,
+    tick: u64,
+}
+
+impl MockBackend {
+    pub fn new(count: usize) -> Self {
+        Self { count, tick: 0 }
+    }
+}
+
+impl GpuBackend for MockBackend {
+    fn sample(&mut self) -> Vec<GpuStats> {
+        self.tick += 1;
+        (0..self.count)
+            .map(|index| {
+                let phase = self.tick as f64 / 20.0 + index as f64;
+                let usage = 50.0 + 45.0 * phase.sin();
+                let total = 16 * 1024 * 1024 * 1024;
+                GpuStats {
+                    model: format!("Mock GPU {}", index),
+                    usage,
+                    temperature: Some(40.0 + usage * 0.4),
+                    power_watts: Some(30.0 + usage * 2.0),
+                    ..Default::default()
+                }
+                .with_memory(total / 8 * (index as u64 % 8 + 1) / 2, total)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_backend_enumerates_every_device() {
+        let mut backend = MockBackend::new(4);
+        let devices = backend.sample();
+        assert_eq!(devices.len(), 4);
+        for (i, device) in devices.iter().enumerate() {
+            assert_eq!(device.model, format!("Mock GPU {}", i));
+            assert!((0.0..=100.0).contains(&device.usage));
+            assert!(device.memory_used <= device.memory_total);
+            assert!(device.temperature.is_some());
+        }
+        // Os valores mudam a cada leitura
+        assert_ne!(backend.sample()[0].usage, devices[0].usage);
+    }
+
+    #[test]
+    fn reads_amdgpu_busy_vram_and_edge_temperature() {
+        let root = sysfs::fixture(
+            "gpu-amd",
+            &[
+                ("class/drm/card1/device/vendor", "0x1002\n"),
+                (
+                    "class/drm/card1/device/product_name",
+                    "Radeon RX 7900 XTX\n",
+                ),
+                ("class/drm/card1/device/gpu_busy_percent", "37\n"),
+                ("class/drm/card1/device/mem_info_vram_used", "1073741824\n"),
+                ("class/drm/card1/device/mem_info_vram_total", "4294967296\n"),
+                ("class/drm/card1/device/hwmon/hwmon3/temp1_input", "51000\n"),
+                ("class/drm/card1/device/hwmon/hwmon3/temp1_label", "edge\n"),
+                ("class/drm/card1/device/hwmon/hwmon3/temp2_input", "63000\n"),
+                (
+                    "class/drm/card1/device/hwmon/hwmon3/temp2_label",
+                    "junction\n",
+                ),
+                // Conector e placa de outro fabricante são ignorados
+                ("class/drm/card1-DP-1/status", "connected\n"),
+                ("class/drm/card0/device/vendor", "0x8086\n"),
+            ],
+        );
+
+        let devices = AmdSysfsBackend::new(&root.join("class/drm")).sample();
+        assert_eq!(devices.len(), 1);
+        let gpu = &devices[0];
+        assert_eq!(gpu.model, "Radeon RX 7900 XTX");
+        assert_eq!(gpu.usage, 37.0);
+        assert_eq!(gpu.memory_used, 1 << 30);
+        assert_eq!(gpu.memory_percent, 25.0);
+        assert_eq!(gpu.temperature, Some(51.0));
+    }
+
+    #[test]
+    fn intel_i915_usage_comes_from_rc6_residency() {
+        let root = sysfs::fixture(
+            "gpu-i915",
+            &[
+                ("class/drm/card0/device/vendor", "0x8086\n"),
+                ("class/drm/card0/device/device", "0x46a6\n"),
+                ("class/drm/card0/gt_act_freq_mhz", "1100\n"),
+                ("class/drm/card0/gt_max_freq_mhz", "1400\n"),
+                ("class/drm/card0/gt/gt0/rc6_residency_ms", "10000\n"),
+            ],
+        );
+        let mut backend = IntelSysfsBackend::new(&root.join("class/drm"));
+        let start = Instant::now();
+
+        let devices = backend.sample_at(start);
+        assert_eq!(devices[0].model, "Intel GPU (0x46a6)");
+        assert_eq!(devices[0].usage, 0.0);
+        assert_eq!(devices[0].frequency_mhz, Some(1100.0));
+        assert_eq!(devices[0].max_frequency_mhz, Some(1400.0));
+
+        // 250 ms ociosa em 1 s: 75% de uso
+        sysfs::write_fixture(&root, "class/drm/card0/gt/gt0/rc6_residency_ms", "10250\n");
+        let devices = backend.sample_at(start + std::time::Duration::from_secs(1));
+        assert!((devices[0].usage - 75.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn intel_xe_reads_per_gt_files() {
+        let root = sysfs::fixture(
+            "gpu-xe",
+            &[
+                ("class/drm/card0/device/vendor", "0x8086\n"),
+                ("class/drm/card0/device/tile0/gt0/freq0/act_freq", "2050\n"),
+                ("class/drm/card0/device/tile0/gt0/freq0/max_freq", "2400\n"),
+                (
+                    "class/drm/card0/device/tile0/gt0/gtidle/idle_residency_ms",
+                    "0\n",
+                ),
+                ("class/drm/card0/device/hwmon/hwmon2/temp1_input", "48500\n"),
+            ],
+        );
+        let mut backend = IntelSysfsBackend::new(&root.join("class/drm"));
+        let start = Instant::now();
+
+        backend.sample_at(start);
+        sysfs::write_fixture(
+            &root,
+            "class/drm/card0/device/tile0/gt0/gtidle/idle_residency_ms",
+            "500\n",
+        );
+        let devices = backend.sample_at(start + std::time::Duration::from_secs(2));
+        assert_eq!(devices[0].model, "Intel GPU");
+        assert!((devices[0].usage - 75.0).abs() < 1e-6);
+        assert_eq!(devices[0].frequency_mhz, Some(2050.0));
+        assert_eq!(devices[0].temperature, Some(48.5));
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 use sysinfo::{CpuExt, System, SystemExt};
 use tui::style::Color;
@@ -5,17 +6,16 @@ use tui::style::Color;
 #[cfg(target_os = "linux")]
 use std::fs::read_to_string;
 
-#[cfg(target_os = "windows")]
-use serde::Deserialize;
-
+#[allow(dead_code)]
 #[derive(Clone)]
 pub enum GpuType {
     Nvidia,
     Amd,
     Intel,
+    Unknown,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
     pub cpu_model: String,
     pub cpu_cores: usize,
@@ -76,12 +76,21 @@ impl SystemInfo {
     }
 }
 
+/// Com várias placas, o modelo da primeira seguido da quantidade (ex.: "A100 x8").
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn nvidia_model(nvml: &nvml_wrapper::Nvml, first: String) -> String {
+    match nvml.device_count() {
+        Ok(count) if count > 1 => format!("{} x{}", first, count),
+        _ => first,
+    }
+}
+
 #[cfg(target_os = "linux")]
 fn detect_gpu() -> Result<(GpuType, String), Box<dyn Error>> {
     // Tentar NVIDIA primeiro
     if let Ok(nvml) = nvml_wrapper::Nvml::init() {
         if let Ok(device) = nvml.device_by_index(0) {
-            return Ok((GpuType::Nvidia, device.name()?.to_string()));
+            return Ok((GpuType::Nvidia, nvidia_model(&nvml, device.name()?)));
         }
     }
 
@@ -111,10 +120,10 @@ fn detect_gpu() -> Result<(GpuType, String), Box<dyn Error>> {
     let model = output
         .lines()
         .find(|line| {
-            line.contains("Chip") || 
-            line.contains("Model") || 
-            line.contains("Chipset") ||
-            line.contains("Processor")
+            line.contains("Chip")
+                || line.contains("Model")
+                || line.contains("Chipset")
+                || line.contains("Processor")
         })
         .and_then(|line| line.split(':').nth(1))
         .map(|s| s.trim())
@@ -145,7 +154,7 @@ fn detect_gpu() -> Result<(GpuType, String), Box<dyn Error>> {
     // Tentar NVIDIA primeiro
     if let Ok(nvml) = nvml_wrapper::Nvml::init() {
         if let Ok(device) = nvml.device_by_index(0) {
-            return Ok((GpuType::Nvidia, device.name()?.to_string()));
+            return Ok((GpuType::Nvidia, nvidia_model(&nvml, device.name()?)));
         }
     }
 
@@ -0,0 +1,503 @@
+use serde::Deserialize;
+use std::error::Error;
+use tui::layout::Rect;
+
+use crate::ChartKind;
+
+/// Nomes dos gráficos na configuração; `cpu` segue a tecla `c` (total ou por núcleo).
+const CHARTS: [(&str, ChartKind); 14] = [
+    ("cpu", ChartKind::Cpu),
+    ("cpu_time", ChartKind::CpuTime),
+    ("load", ChartKind::Load),
+    ("gpu", ChartKind::Gpu),
+    ("memory", ChartKind::Memory),
+    ("pressure", ChartKind::Pressure),
+    ("swap", ChartKind::Swap),
+    ("network", ChartKind::Network),
+    ("disk_io", ChartKind::DiskIo),
+    ("temperature", ChartKind::Temperature),
+    ("battery", ChartKind::Discharge),
+    ("power", ChartKind::Power),
+    ("frequency", ChartKind::Frequency),
+    ("cgroup", ChartKind::Cgroup),
+];
+
+const PANELS: [(&str, Widget); 5] = [
+    ("charts", Widget::Charts),
+    ("stats", Widget::Stats),
+    ("processes", Widget::Processes),
+    ("disks", Widget::Disks),
+    ("sensors", Widget::Sensors),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Widget {
+    Chart(ChartKind),
+    /// Todos os gráficos visíveis que não têm lugar próprio, empilhados
+    Charts,
+    Stats,
+    /// Tabela de processos, ou o painel de cgroups quando ativo
+    Processes,
+    Disks,
+    Sensors,
+}
+
+impl Widget {
+    fn parse(name: &str) -> Option<Widget> {
+        CHARTS
+            .iter()
+            .find(|(chart, _)| *chart == name)
+            .map(|(_, kind)| Widget::Chart(kind.clone()))
+            .or_else(|| {
+                PANELS
+                    .iter()
+                    .find(|(panel, _)| *panel == name)
+                    .map(|(_, widget)| widget.clone())
+            })
+    }
+
+    fn name(&self) -> &'static str {
+        let name = match self {
+            Widget::Chart(kind) => CHARTS
+                .iter()
+                .find(|(_, chart)| chart == kind)
+                .map(|(name, _)| name),
+            widget => PANELS
+                .iter()
+                .find(|(_, panel)| panel == widget)
+                .map(|(name, _)| name),
+        };
+        name.map_or("?", |name| name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Size {
+    /// Divide o espaço que sobra com os outros sem tamanho
+    Fill,
+    Percent(u16),
+    /// Linhas (em `rows`) ou colunas (em `columns`) do terminal
+    Cells(u16),
+    /// O necessário para o conteúdo (discos e sensores), até metade do espaço
+    Auto,
+}
+
+impl Size {
+    fn parse(size: &SizeConfig) -> Result<Size, String> {
+        let text = match size {
+            SizeConfig::Cells(0) => return Err("size must be greater than 0".to_string()),
+            SizeConfig::Cells(cells) => return Ok(Size::Cells(*cells)),
+            SizeConfig::Text(text) => text.trim(),
+        };
+        if text == "auto" {
+            return Ok(Size::Auto);
+        }
+        if let Some(percent) = text.strip_suffix('%') {
+            return match percent.trim().parse() {
+                Ok(percent @ 1..=100) => Ok(Size::Percent(percent)),
+                _ => Err(format!("invalid percentage '{}'", text)),
+            };
+        }
+        match text.parse() {
+            Ok(cells @ 1..) => Ok(Size::Cells(cells)),
+            _ => Err(format!(
+                "invalid size '{}' (expected \"N%\", a number of cells or \"auto\")",
+                text
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LayoutNode {
+    Widget(Widget, Size),
+    /// Filhos lado a lado (`columns`) ou um embaixo do outro (`rows`)
+    Split {
+        horizontal: bool,
+        size: Size,
+        children: Vec<LayoutNode>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NodeConfig {
+    #[serde(default)]
+    widget: Option<String>,
+    #[serde(default)]
+    size: Option<SizeConfig>,
+    #[serde(default)]
+    rows: Option<Vec<NodeConfig>>,
+    #[serde(default)]
+    columns: Option<Vec<NodeConfig>>,
+}
+
+#[derive(Deserialize)]
+#[serde(
+    untagged,
+    expecting = "expected a size: \"N%\", a number of cells or \"auto\""
+)]
+enum SizeConfig {
+    Cells(u16),
+    Text(String),
+}
+
+/// Lê a seção `[layout]` da configuração; sem ela, a disposição padrão.
+///
+/// ```toml
+/// [layout]
+/// columns = [
+///     { rows = [{ widget = "cpu" }, { widget = "memory" }] },
+///     { widget = "processes", size = "40%" },
+///     { widget = "stats", size = 32 },
+/// ]
+/// ```
+pub fn parse(config: Option<&toml::Value>) -> Result<LayoutNode, Box<dyn Error>> {
+    let Some(config) = config else {
+        return Ok(LayoutNode::default());
+    };
+    let node: NodeConfig = config
+        .clone()
+        .try_into()
+        .map_err(|e| format!("Invalid layout: {}", e.to_string().trim_end()))?;
+    let node =
+        LayoutNode::from_config(node, "layout").map_err(|e| format!("Invalid layout: {}", e))?;
+
+    let mut widgets = Vec::new();
+    node.widgets(&mut widgets);
+    for (i, widget) in widgets.iter().enumerate() {
+        if widgets[..i].contains(widget) {
+            return Err(format!(
+                "Invalid layout: widget '{}' is used more than once",
+                widget.name()
+            )
+            .into());
+        }
+    }
+    Ok(node)
+}
+
+impl Default for LayoutNode {
+    /// Gráficos à esquerda, processos, discos e sensores no meio e as estatísticas à direita.
+    fn default() -> Self {
+        LayoutNode::Split {
+            horizontal: true,
+            size: Size::Fill,
+            children: vec![
+                LayoutNode::Widget(Widget::Charts, Size::Fill),
+                LayoutNode::Split {
+                    horizontal: false,
+                    size: Size::Percent(35),
+                    children: vec![
+                        LayoutNode::Widget(Widget::Processes, Size::Fill),
+                        LayoutNode::Widget(Widget::Disks, Size::Auto),
+                        LayoutNode::Widget(Widget::Sensors, Size::Auto),
+                    ],
+                },
+                LayoutNode::Widget(Widget::Stats, Size::Percent(20)),
+            ],
+        }
+    }
+}
+
+impl LayoutNode {
+    fn from_config(config: NodeConfig, path: &str) -> Result<LayoutNode, String> {
+        let size = match &config.size {
+            Some(size) => Size::parse(size).map_err(|e| format!("{}: {}", path, e))?,
+            None => Size::Fill,
+        };
+        let (children, horizontal, key) = match (config.widget, config.rows, config.columns) {
+            (Some(name), None, None) => {
+                return match Widget::parse(&name) {
+                    Some(widget) => Ok(LayoutNode::Widget(widget, size)),
+                    None => Err(format!(
+                        "{}: unknown widget '{}' (expected one of: {})",
+                        path,
+                        name,
+                        CHARTS
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .chain(PANELS.iter().map(|(name, _)| *name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )),
+                };
+            }
+            (None, Some(rows), None) => (rows, false, "rows"),
+            (None, None, Some(columns)) => (columns, true, "columns"),
+            _ => {
+                return Err(format!(
+                    "{}: expected exactly one of widget, rows or columns",
+                    path
+                ))
+            }
+        };
+        if children.is_empty() {
+            return Err(format!("{}.{}: is empty", path, key));
+        }
+
+        let children = children
+            .into_iter()
+            .enumerate()
+            .map(|(i, child)| LayoutNode::from_config(child, &format!("{}.{}[{}]", path, key, i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let percent: u16 = children
+            .iter()
+            .filter_map(|child| match child.size() {
+                Size::Percent(percent) => Some(percent),
+                _ => None,
+            })
+            .sum();
+        if percent > 100 {
+            return Err(format!("{}.{}: sizes add up to {}%", path, key, percent));
+        }
+        Ok(LayoutNode::Split {
+            horizontal,
+            size,
+            children,
+        })
+    }
+
+    fn size(&self) -> Size {
+        match self {
+            LayoutNode::Widget(_, size) | LayoutNode::Split { size, .. } => *size,
+        }
+    }
+
+    fn widgets(&self, widgets: &mut Vec<Widget>) {
+        match self {
+            LayoutNode::Widget(widget, _) => widgets.push(widget.clone()),
+            LayoutNode::Split { children, .. } => {
+                for child in children {
+                    child.widgets(widgets);
+                }
+            }
+        }
+    }
+
+    /// Gráficos com lugar próprio, que ficam fora de `charts`.
+    pub fn placed_charts(&self) -> Vec<ChartKind> {
+        let mut widgets = Vec::new();
+        self.widgets(&mut widgets);
+        let mut charts = Vec::new();
+        for widget in widgets {
+            if let Widget::Chart(kind) = widget {
+                if kind == ChartKind::Cpu {
+                    charts.push(ChartKind::CpuCores);
+                }
+                charts.push(kind);
+            }
+        }
+        charts
+    }
+
+    /// Posiciona os widgets visíveis em `area`; os ocultos cedem o espaço aos vizinhos.
+    /// `auto` dá a altura (ou largura) desejada dos widgets com tamanho `auto`.
+    pub fn place(
+        &self,
+        area: Rect,
+        visible: &dyn Fn(&Widget) -> bool,
+        auto: &dyn Fn(&Widget) -> Option<u16>,
+    ) -> Vec<(Widget, Rect)> {
+        let mut placed = Vec::new();
+        self.place_into(area, visible, auto, &mut placed);
+        placed
+    }
+
+    fn is_visible(&self, visible: &dyn Fn(&Widget) -> bool) -> bool {
+        match self {
+            LayoutNode::Widget(widget, _) => visible(widget),
+            LayoutNode::Split { children, .. } => {
+                children.iter().any(|child| child.is_visible(visible))
+            }
+        }
+    }
+
+    fn place_into(
+        &self,
+        area: Rect,
+        visible: &dyn Fn(&Widget) -> bool,
+        auto: &dyn Fn(&Widget) -> Option<u16>,
+        placed: &mut Vec<(Widget, Rect)>,
+    ) {
+        match self {
+            LayoutNode::Widget(widget, _) => placed.push((widget.clone(), area)),
+            LayoutNode::Split {
+                horizontal,
+                children,
+                ..
+            } => {
+                let children: Vec<&LayoutNode> = children
+                    .iter()
+                    .filter(|child| child.is_visible(visible))
+                    .collect();
+                let sizes: Vec<(Size, Option<u16>)> = children
+                    .iter()
+                    .map(|child| match child {
+                        LayoutNode::Widget(widget, Size::Auto) => (Size::Auto, auto(widget)),
+                        child => (child.size(), None),
+                    })
+                    .collect();
+                let areas = split(area, *horizontal, &sizes);
+                for (child, area) in children.into_iter().zip(areas) {
+                    child.place_into(area, visible, auto, placed);
+                }
+            }
+        }
+    }
+}
+
+/// Divide `area` entre os filhos: tamanhos fixos e percentuais primeiro, depois `auto`
+/// (até metade do espaço) e o resto em partes iguais entre os que não têm tamanho.
+/// Sem nenhum desses, o último filho fica com a sobra.
+pub fn split(area: Rect, horizontal: bool, sizes: &[(Size, Option<u16>)]) -> Vec<Rect> {
+    let total = if horizontal { area.width } else { area.height };
+    let mut lengths: Vec<Option<u16>> = sizes
+        .iter()
+        .map(|(size, hint)| match (size, hint) {
+            (Size::Cells(cells), _) => Some(*cells),
+            (Size::Percent(percent), _) => Some((total as u32 * *percent as u32 / 100) as u16),
+            (Size::Auto, Some(hint)) => Some((*hint).min(total / 2)),
+            (Size::Auto, None) | (Size::Fill, _) => None,
+        })
+        .collect();
+
+    // O que não couber é cortado, na ordem dos filhos
+    let mut remaining = total;
+    for length in lengths.iter_mut().flatten() {
+        *length = (*length).min(remaining);
+        remaining -= *length;
+    }
+    let fill = lengths.iter().filter(|length| length.is_none()).count() as u16;
+    let mut extra = if fill > 0 { remaining % fill } else { 0 };
+    let mut lengths: Vec<u16> = lengths
+        .into_iter()
+        .map(|length| {
+            length.unwrap_or_else(|| {
+                let share = remaining / fill + u16::from(extra > 0);
+                extra = extra.saturating_sub(1);
+                share
+            })
+        })
+        .collect();
+    if fill == 0 {
+        if let Some(last) = lengths.last_mut() {
+            *last += remaining;
+        }
+    }
+
+    let mut offset = 0;
+    lengths
+        .into_iter()
+        .map(|length| {
+            let rect = if horizontal {
+                Rect {
+                    x: area.x + offset,
+                    width: length,
+                    ..area
+                }
+            } else {
+                Rect {
+                    y: area.y + offset,
+                    height: length,
+                    ..area
+                }
+            };
+            offset += length;
+            rect
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(contents: &str) -> Result<LayoutNode, Box<dyn Error>> {
+        let value: toml::Value = toml::from_str(contents).unwrap();
+        parse(value.get("layout"))
+    }
+
+    #[test]
+    fn default_layout_gives_the_middle_column_space_to_the_charts() {
+        let area = Rect::new(0, 0, 100, 40);
+        let placed = LayoutNode::default().place(area, &|_| true, &|_| Some(5));
+        let widths: Vec<(Widget, u16)> = placed
+            .iter()
+            .map(|(widget, rect)| (widget.clone(), rect.width))
+            .collect();
+        assert_eq!(widths[0], (Widget::Charts, 45));
+        assert_eq!(widths[4], (Widget::Stats, 20));
+        // Processos com o que sobra depois dos discos e sensores
+        assert_eq!(placed[1].1.height, 30);
+        assert_eq!(placed[3].1, Rect::new(45, 35, 35, 5));
+
+        let placed = LayoutNode::default().place(
+            area,
+            &|widget| matches!(widget, Widget::Charts | Widget::Stats),
+            &|_| None,
+        );
+        assert_eq!(placed.len(), 2);
+        assert_eq!((placed[0].1.width, placed[1].1.width), (80, 20));
+    }
+
+    #[test]
+    fn reads_nested_splits_and_sizes() {
+        let node = layout(
+            r#"
+            [layout]
+            rows = [
+                { size = 10, columns = [{ widget = "cpu" }, { widget = "memory", size = "25%" }] },
+                { widget = "charts" },
+                { widget = "disks", size = "auto" },
+            ]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            node.placed_charts(),
+            [ChartKind::CpuCores, ChartKind::Cpu, ChartKind::Memory]
+        );
+
+        let placed = node.place(Rect::new(0, 0, 80, 30), &|_| true, &|_| Some(4));
+        let rects: Vec<Rect> = placed.into_iter().map(|(_, rect)| rect).collect();
+        assert_eq!(
+            rects,
+            [
+                Rect::new(0, 0, 60, 10),
+                Rect::new(60, 0, 20, 10),
+                Rect::new(0, 10, 80, 16),
+                Rect::new(0, 26, 80, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_layout_errors() {
+        let error = |contents: &str| layout(contents).err().unwrap().to_string();
+        assert!(error("[layout]\ncolumns = [{ widget = \"cpus\" }]")
+            .contains("layout.columns[0]: unknown widget 'cpus'"));
+        assert!(error("[layout]\nrows = []").contains("layout.rows: is empty"));
+        assert!(
+            error("[layout]\nwidget = \"cpu\"\nrows = [{ widget = \"stats\" }]")
+                .contains("exactly one of widget, rows or columns")
+        );
+        assert!(error(
+            "[layout]\ncolumns = [{ widget = \"cpu\", size = \"70%\" }, { widget = \"stats\", size = \"40%\" }]"
+        )
+        .contains("sizes add up to 110%"));
+        assert!(
+            error("[layout]\nrows = [{ widget = \"stats\", size = \"big\" }]")
+                .contains("invalid size 'big'")
+        );
+        assert!(
+            error("[layout]\nrows = [{ widget = \"stats\" }, { widget = \"stats\" }]")
+                .contains("used more than once")
+        );
+        assert!(error("[layout]\nrows = [{ widgte = \"stats\" }]").contains("unknown field"));
+        assert!(
+            error("[layout]\nrows = [{ widget = \"stats\", size = true }]")
+                .contains("expected a size")
+        );
+    }
+}
@@ -1,5 +1,5 @@
-use chrono::Local;
-use clap::Parser;
+use chrono::{DateTime, Local, TimeZone};
+use clap::{Parser, Subcommand, ValueEnum};
 use config::{Config, File};
 use crossterm::{
     event::{self, Event, KeyCode},
@@ -8,16 +8,16 @@ use crossterm::{
 };
 use directories::ProjectDirs;
 use humansize::{format_size, BINARY};
-use nvml_wrapper::{enum_wrappers::device::TemperatureSensor, Nvml};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::io;
-use std::path::PathBuf;
-use std::time::Duration;
-use sysinfo::{CpuExt, NetworkExt, System, SystemExt};
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use sysinfo::{CpuExt, System, SystemExt};
 use tui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
+    layout::Margin,
     style::{Color, Modifier, Style},
     symbols,
     text::{Span, Spans},
@@ -25,21 +25,91 @@ use tui::{
     Terminal,
 };
 
+mod alerts;
+mod cgroup;
+mod cpufreq;
+mod disk;
+mod fleet;
+mod gpu;
 mod hardware;
+mod layout;
+mod metrics;
+mod network;
+mod power;
+mod pressure;
+mod process;
+mod procstat;
+mod rapl;
+mod record;
+mod remote;
+mod replay;
+mod sensors;
+mod snapshot;
+mod sysfs;
+use crate::alerts::{AlertConfig, Alerts, Metric};
+use crate::cgroup::{CgroupMode, CgroupScanner, CgroupStats, CgroupTable, CgroupTracker};
+use crate::cpufreq::{CoreFrequency, ThrottleDetector, ThrottleReason};
+use crate::disk::{DiskIo, DiskIoTracker, DiskUsage};
+use crate::fleet::Fleet;
+use crate::gpu::{BackendKind, GpuBackend, GpuStats};
 use crate::hardware::SystemInfo;
+use crate::layout::{LayoutNode, Size, Widget};
+use crate::metrics::MetricsServer;
+use crate::network::{InterfaceStats, NetworkTracker};
+use crate::power::PowerStatus;
+use crate::pressure::PressureStats;
+use crate::process::ProcessTable;
+use crate::procstat::{CpuBreakdown, KernelActivity, StatTracker};
+use crate::rapl::{CpuPower, RaplTracker, SessionEnergy};
+use crate::record::Recorder;
+use crate::remote::{AgentServer, RemoteClient};
+use crate::replay::Replay;
+use crate::sensors::{Sensor, SensorKind};
+use crate::snapshot::{CpuSnapshot, MemorySnapshot, NetworkSnapshot, Snapshot, SystemSnapshot};
 
 /// System resource monitor
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Disable GPU monitoring
     #[arg(long)]
     no_gpu: bool,
 
+    /// Where GPU metrics come from
+    #[arg(long, value_enum, default_value_t = BackendKind::Auto)]
+    gpu_backend: BackendKind,
+
     /// Disable network monitoring
     #[arg(long)]
     no_network: bool,
 
+    /// Hide the process table
+    #[arg(long)]
+    no_processes: bool,
+
+    /// Show the cgroup/container panel in place of the process table
+    #[arg(long)]
+    cgroups: bool,
+
+    /// Disable disk usage and I/O monitoring
+    #[arg(long)]
+    no_disks: bool,
+
+    /// Hide the sensors panel and the temperature chart
+    #[arg(long)]
+    no_sensors: bool,
+
+    /// Hide CPU/GPU power draw, the energy total and the power chart
+    #[arg(long)]
+    no_power: bool,
+
+    /// Compute memory and CPU percentages against a cgroup v2 quota: our own (auto), a cgroup path, or none (off)
+    #[arg(long, value_name = "auto|PATH|off", value_parser = cgroup::parse_mode)]
+    cgroup: Option<CgroupMode>,
+
     /// Update interval in milliseconds
     #[arg(long, default_value_t = 50)]
     interval: u64,
@@ -48,6 +118,10 @@ struct Args {
     #[arg(long, default_value_t = 100)]
     history: usize,
 
+    /// Start with one CPU line per logical core instead of the aggregate
+    #[arg(long)]
+    per_core: bool,
+
     /// Save current settings as default
     #[arg(long)]
     save_config: bool,
@@ -55,14 +129,97 @@ struct Args {
     /// Reset settings to default
     #[arg(long)]
     reset_config: bool,
+
+    /// Output format: the interactive interface, one JSON object per sample on stdout, or nothing
+    #[arg(long, value_enum, default_value_t = OutputFormat::Tui)]
+    format: OutputFormat,
+
+    /// Record every sample to this file: CSV rows for `.csv`, a replayable session otherwise
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+
+    /// Rotate the CSV file when it reaches this size in MiB
+    #[arg(long, value_name = "MIB", requires = "record", value_parser = clap::value_parser!(u64).range(1..=u64::MAX >> 20))]
+    record_max_size: Option<u64>,
+
+    /// Start a new CSV file every day (the date is added to the file name)
+    #[arg(long, requires = "record")]
+    record_daily: bool,
+
+    /// Play back a session saved with --record instead of reading this machine
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["record", "format"])]
+    replay: Option<PathBuf>,
+
+    /// Expose the latest sample as Prometheus metrics at http://ADDR:PORT/metrics
+    #[arg(long, value_name = "ADDR:PORT", conflicts_with = "replay")]
+    serve_metrics: Option<SocketAddr>,
+
+    /// Show the samples streamed by `monitor agent` on HOST:PORT instead of this machine
+    #[arg(
+        long,
+        value_name = "HOST:PORT",
+        conflicts_with_all = ["replay", "format"]
+    )]
+    connect: Option<String>,
+
+    /// Shared secret: required by `monitor agent`, sent by --connect
+    #[arg(long, global = true)]
+    token: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Collect without the interface and stream samples to `monitor --connect` clients over TCP
+    Agent {
+        /// Address to accept clients on
+        #[arg(long, value_name = "ADDR:PORT")]
+        listen: SocketAddr,
+
+        /// Sampling interval in milliseconds (default: the usual --interval, or 1000 if unset)
+        #[arg(long, value_name = "MS")]
+        interval: Option<u64>,
+    },
+    /// Overview of many agents, one row per host; Enter opens the full view of a host
+    Fleet {
+        /// TOML file listing the agents (`hosts = ["host:port", ...]`)
+        hosts: PathBuf,
+    },
+}
+
+// Quanto `--connect` espera pela primeira amostra antes de desistir
+const CONNECT_WAIT: Duration = Duration::from_secs(10);
+// Intervalo do `monitor agent` quando nem a configuração nem a linha de comando escolhem um
+const AGENT_INTERVAL_MS: u64 = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Tui,
+    #[value(alias = "ndjson")]
+    Json,
+    /// Só coleta (útil com --record)
+    None,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 struct AppConfig {
     no_gpu: bool,
     no_network: bool,
+    no_processes: bool,
+    cgroups: bool,
+    no_disks: bool,
+    no_sensors: bool,
+    no_power: bool,
+    cgroup: CgroupMode,
     interval: u64,
     history: usize,
+    per_core: bool,
+    /// Onde ler o sysfs (GPUs AMD/Intel); útil para apontar para uma cópia
+    sysfs_root: PathBuf,
+    alerts: Vec<AlertConfig>,
+    /// Disposição dos painéis (seção `[layout]`), validada ao iniciar
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layout: Option<toml::Value>,
 }
 
 impl Default for AppConfig {
@@ -70,25 +227,33 @@ impl Default for AppConfig {
         Self {
             no_gpu: false,
             no_network: false,
+            no_processes: false,
+            cgroups: false,
+            no_disks: false,
+            no_sensors: false,
+            no_power: false,
+            cgroup: CgroupMode::Auto,
             interval: 50,
             history: 100,
+            per_core: false,
+            sysfs_root: PathBuf::from(sysfs::DEFAULT_ROOT),
+            alerts: Vec::new(),
+            layout: None,
         }
     }
 }
 
 impl AppConfig {
-    fn load() -> Self {
-        if let Some(config_path) = get_config_path() {
-            if config_path.exists() {
-                if let Ok(config) = Config::builder()
-                    .add_source(File::with_name(config_path.to_str().unwrap()))
-                    .build()
-                {
-                    return config.try_deserialize().unwrap_or_default();
-                }
-            }
-        }
-        Self::default()
+    /// Lê o arquivo de configuração; um arquivo inválido é um erro, não os padrões.
+    fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let Some(config_path) = get_config_path().filter(|path| path.exists()) else {
+            return Ok(Self::default());
+        };
+        Config::builder()
+            .add_source(File::from(config_path.as_path()))
+            .build()
+            .and_then(|config| config.try_deserialize())
+            .map_err(|e| format!("Invalid configuration: {}", e).into())
     }
 
     fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
@@ -112,12 +277,33 @@ impl AppConfig {
         if args.no_network {
             self.no_network = true;
         }
+        if args.no_processes {
+            self.no_processes = true;
+        }
+        if args.cgroups {
+            self.cgroups = true;
+        }
+        if args.no_disks {
+            self.no_disks = true;
+        }
+        if args.no_sensors {
+            self.no_sensors = true;
+        }
+        if args.no_power {
+            self.no_power = true;
+        }
+        if let Some(cgroup) = &args.cgroup {
+            self.cgroup = cgroup.clone();
+        }
         if args.interval != 50 {
             self.interval = args.interval;
         }
         if args.history != 100 {
             self.history = args.history;
         }
+        if args.per_core {
+            self.per_core = true;
+        }
     }
 }
 
@@ -126,55 +312,554 @@ fn get_config_path() -> Option<PathBuf> {
         .map(|proj_dirs| proj_dirs.config_dir().join("config.toml"))
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 enum ChartKind {
     Cpu,
+    CpuCores,
     Memory,
     Gpu,
     Swap,
+    DiskIo,
+    Network,
+    Temperature,
+    Discharge,
+    Power,
+    Frequency,
+    CpuTime,
+    Load,
+    Pressure,
+    Cgroup,
+}
+
+// Paleta usada quando um gráfico tem várias séries (ex.: um núcleo por linha)
+const SERIES_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::LightBlue,
+    Color::LightCyan,
+    Color::Blue,
+    Color::LightMagenta,
+    Color::White,
+];
+
+#[derive(Clone)]
+struct Series {
+    name: String,
+    data: Vec<(f64, f64)>,
+    color: Color,
 }
 
 #[derive(Clone)]
 struct Graph {
     graph_type: ChartKind,
-    data: Vec<(f64, f64)>,
+    series: Vec<Series>,
+    // Prefixo das séries exibidas (ex.: "eth0" mostra "eth0 RX" e "eth0 TX")
+    selected: Option<String>,
     title: String,
     color: Color,
+    // Pontos em que a CPU estava em throttling (só nos gráficos de CPU)
+    throttled: Vec<(f64, f64)>,
+    throttling: Option<ThrottleReason>,
 }
 
 impl Graph {
     fn new(graph_type: ChartKind) -> Self {
         let (title, color) = match graph_type {
             ChartKind::Cpu => ("CPU Usage", Color::Cyan),
+            ChartKind::CpuCores => ("CPU Usage per Core", Color::Cyan),
             ChartKind::Memory => ("Memory Usage", Color::Yellow),
             ChartKind::Gpu => ("GPU Usage", Color::Green),
             ChartKind::Swap => ("SWAP Usage", Color::Magenta),
+            ChartKind::DiskIo => ("Disk I/O", Color::LightGreen),
+            ChartKind::Network => ("Network", Color::Blue),
+            ChartKind::Temperature => ("Temperature", Color::LightRed),
+            ChartKind::Discharge => ("Battery Discharge", Color::LightYellow),
+            ChartKind::Power => ("Power Draw", Color::LightMagenta),
+            ChartKind::Frequency => ("CPU Frequency", Color::LightCyan),
+            ChartKind::CpuTime => ("CPU Time", Color::Cyan),
+            ChartKind::Load => ("Load Average", Color::LightBlue),
+            ChartKind::Pressure => ("Pressure Stall", Color::LightRed),
+            ChartKind::Cgroup => ("Cgroup", Color::LightBlue),
+        };
+        let selected = match graph_type {
+            ChartKind::Network => Some("total".to_string()),
+            ChartKind::CpuTime => Some("all".to_string()),
+            _ => None,
         };
         Self {
             graph_type,
-            data: vec![(0.0, 0.0)],
+            series: Vec::new(),
+            selected,
             title: title.to_string(),
             color,
+            throttled: Vec::new(),
+            throttling: None,
         }
     }
 
-    fn update(&mut self, data: &SystemData) {
-        let value = match self.graph_type {
-            ChartKind::Cpu => data.cpu_current,
-            ChartKind::Memory => data.mem_current,
-            ChartKind::Gpu => data.gpu_current,
+    fn values(&self, data: &SystemData) -> Vec<(String, f64)> {
+        let single = |value: f64| vec![(self.title.clone(), value)];
+        match self.graph_type {
+            ChartKind::Cpu => single(data.cpu_current),
+            ChartKind::CpuCores => data
+                .cpu_cores_current
+                .iter()
+                .enumerate()
+                .map(|(i, &usage)| (format!("cpu{}", i), usage))
+                .collect(),
+            ChartKind::Memory => single(data.mem_current),
+            ChartKind::Gpu => data
+                .gpus
+                .iter()
+                .enumerate()
+                .flat_map(|(i, gpu)| {
+                    [
+                        (format!("gpu{} usage", i), gpu.usage),
+                        (format!("gpu{} mem", i), gpu.memory_percent),
+                        (format!("gpu{} temp", i), gpu.temperature.unwrap_or(0.0)),
+                    ]
+                })
+                .collect(),
             ChartKind::Swap => {
                 if data.swap_total > 0 {
-                    (data.swap_used as f64 / data.swap_total as f64) * 100.0
+                    single((data.swap_used as f64 / data.swap_total as f64) * 100.0)
                 } else {
-                    0.0
+                    single(0.0)
                 }
             }
-        };
-        self.data.push((data.counter, value));
-        if self.data.len() > data.config.history {
-            self.data.remove(0);
+            ChartKind::DiskIo => data
+                .disk_io
+                .iter()
+                .flat_map(|device| {
+                    [
+                        (format!("{} read", device.name), device.read_rate),
+                        (format!("{} write", device.name), device.write_rate),
+                    ]
+                })
+                .collect(),
+            ChartKind::Network => {
+                let (rx, tx) = data
+                    .networks
+                    .iter()
+                    .fold((0.0, 0.0), |(rx, tx), interface| {
+                        (rx + interface.rx_rate, tx + interface.tx_rate)
+                    });
+                let mut values = vec![("total RX".to_string(), rx), ("total TX".to_string(), tx)];
+                for interface in &data.networks {
+                    values.push((format!("{} RX", interface.name), interface.rx_rate));
+                    values.push((format!("{} TX", interface.name), interface.tx_rate));
+                }
+                values
+            }
+            ChartKind::Temperature => data
+                .sensors
+                .iter()
+                .filter(|sensor| sensor.kind == SensorKind::Temperature)
+                .map(|sensor| (sensor.name(), sensor.value))
+                .collect(),
+            // Carregando conta como zero: o gráfico mostra só o consumo da bateria
+            ChartKind::Discharge => data
+                .power
+                .batteries
+                .iter()
+                .map(|battery| {
+                    let watts = battery.watts.unwrap_or(0.0);
+                    let draw = if battery.is_discharging() { watts } else { 0.0 };
+                    (battery.name.clone(), draw)
+                })
+                .collect(),
+            // Só os domínios que a máquina informa; as GPUs entram somadas
+            ChartKind::Power => {
+                let mut values = Vec::new();
+                if let Some(cpu) = data.cpu_power {
+                    values.push(("package".to_string(), cpu.package));
+                    if let Some(core) = cpu.core {
+                        values.push(("core".to_string(), core));
+                    }
+                    if let Some(dram) = cpu.dram {
+                        values.push(("dram".to_string(), dram));
+                    }
+                }
+                if let Some(gpu) = data.gpu_power() {
+                    values.push(("gpu".to_string(), gpu));
+                }
+                values
+            }
+            // Cada estado é desenhado somado aos anteriores, formando faixas empilhadas
+            ChartKind::CpuTime => {
+                let mut values = Vec::new();
+                let cpus = data
+                    .cpu_times
+                    .iter()
+                    .map(|times| ("all".to_string(), times))
+                    .chain(
+                        data.core_times
+                            .iter()
+                            .enumerate()
+                            .map(|(i, times)| (format!("cpu{}", i), times)),
+                    );
+                for (cpu, times) in cpus {
+                    let mut stacked = 0.0;
+                    for (state, share) in times.stacked() {
+                        stacked += share;
+                        values.push((format!("{} {}", cpu, state), stacked));
+                    }
+                }
+                values
+            }
+            // Média de 10s de cada recurso; `full` quando o kernel informa
+            ChartKind::Pressure => data
+                .pressure
+                .resources()
+                .into_iter()
+                .filter_map(|(name, pressure)| Some((name, pressure?)))
+                .flat_map(|(name, pressure)| {
+                    let mut values = vec![(format!("{} some", name), pressure.some.avg10)];
+                    if let Some(full) = pressure.full {
+                        values.push((format!("{} full", name), full.avg10));
+                    }
+                    values
+                })
+                .collect(),
+            ChartKind::Load => ["1m", "5m", "15m"]
+                .iter()
+                .zip(data.load)
+                .map(|(name, load)| (name.to_string(), load))
+                .collect(),
+            ChartKind::Frequency => data
+                .frequencies
+                .iter()
+                .map(|core| (format!("cpu{}", core.cpu), core.current_mhz))
+                .collect(),
+            // O cgroup aberto no painel; memória em % do limite, ou da RAM sem limite
+            ChartKind::Cgroup => data
+                .cgroup_table
+                .drilled()
+                .map(|cgroup| {
+                    let limit = cgroup.memory_max.unwrap_or(data.total_memory);
+                    vec![
+                        (format!("{} cpu", cgroup.name), cgroup.cpu_percent),
+                        (
+                            format!("{} memory", cgroup.name),
+                            snapshot::percent(cgroup.memory_current, limit),
+                        ),
+                    ]
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn visible_series(&self) -> impl Iterator<Item = &Series> {
+        self.series
+            .iter()
+            .filter(move |series| match (&self.selected, &self.graph_type) {
+                (Some(name), ChartKind::Temperature) => &series.name == name,
+                (Some(prefix), _) => series
+                    .name
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.starts_with(' ')),
+                // Sem GPU selecionada, só o uso de cada uma
+                (None, ChartKind::Gpu) => series.name.ends_with(" usage"),
+                (None, _) => true,
+            })
+    }
+
+    fn series_color(&self, index: usize, name: &str, count: usize) -> Color {
+        match self.graph_type {
+            ChartKind::Network if name.ends_with(" RX") => Color::LightBlue,
+            ChartKind::Network => Color::LightRed,
+            ChartKind::CpuTime => match name.rsplit(' ').next() {
+                Some("user") => Color::LightGreen,
+                Some("nice") => Color::Blue,
+                Some("system") => Color::LightRed,
+                Some("irq") => Color::Magenta,
+                Some("softirq") => Color::LightMagenta,
+                Some("iowait") => Color::Yellow,
+                Some("steal") => Color::Red,
+                _ => Color::Cyan,
+            },
+            ChartKind::Pressure => match name.split(' ').next() {
+                Some("cpu") if name.ends_with(" full") => Color::LightCyan,
+                Some("cpu") => Color::Cyan,
+                Some("memory") if name.ends_with(" full") => Color::LightRed,
+                Some("memory") => Color::Yellow,
+                _ if name.ends_with(" full") => Color::LightMagenta,
+                _ => Color::Magenta,
+            },
+            ChartKind::Cgroup if name.ends_with(" memory") => Color::Yellow,
+            ChartKind::Gpu if name.ends_with(" mem") => Color::Yellow,
+            ChartKind::Gpu if name.ends_with(" temp") => Color::LightRed,
+            ChartKind::Gpu if count <= 3 => self.color,
+            ChartKind::Gpu => SERIES_COLORS[index / 3 % SERIES_COLORS.len()],
+            _ if count == 1 => self.color,
+            _ => SERIES_COLORS[index % SERIES_COLORS.len()],
+        }
+    }
+
+    /// Gráficos de taxa (bytes/s) têm o eixo Y ajustado ao maior valor visível.
+    fn is_rate(&self) -> bool {
+        matches!(self.graph_type, ChartKind::DiskIo | ChartKind::Network)
+    }
+
+    fn y_max(&self) -> f64 {
+        let max = self
+            .visible_series()
+            .flat_map(|series| series.data.iter().map(|&(_, v)| v))
+            .fold(0.0, f64::max);
+        match self.graph_type {
+            _ if self.is_rate() => (max * 1.1).max(1024.0),
+            // Temperaturas acima de 100°C ainda precisam caber
+            ChartKind::Temperature => (max * 1.1).max(100.0),
+            ChartKind::Discharge | ChartKind::Power => (max * 1.1).max(10.0),
+            ChartKind::Frequency => (max * 1.1).max(1000.0),
+            ChartKind::Load => (max * 1.1).max(1.0),
+            // CPU em % de um núcleo passa de 100 com vários núcleos ocupados
+            ChartKind::Cgroup => (max * 1.1).max(100.0),
+            _ => 100.0,
+        }
+    }
+
+    /// Rótulo do topo do eixo Y, para gráficos que não são em porcentagem.
+    fn y_label(&self, value: f64) -> Option<String> {
+        match self.graph_type {
+            _ if self.is_rate() => Some(format_rate(value)),
+            ChartKind::Temperature => Some(format!("{:.0}°C", value)),
+            ChartKind::Discharge | ChartKind::Power => Some(format!("{:.1} W", value)),
+            ChartKind::Frequency => Some(cpufreq::format_mhz(value)),
+            ChartKind::Load => Some(format!("{:.2}", value)),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, data: &SystemData) {
+        let (names, values): (Vec<String>, Vec<f64>) = self.values(data).into_iter().unzip();
+
+        // Recriar as séries se elas mudaram (ex.: primeira amostra, disco conectado)
+        if self.series.len() != names.len()
+            || self
+                .series
+                .iter()
+                .zip(&names)
+                .any(|(series, name)| &series.name != name)
+        {
+            let count = names.len();
+            self.series = names
+                .into_iter()
+                .enumerate()
+                .map(|(i, name)| Series {
+                    color: self.series_color(i, &name, count),
+                    name,
+                    data: vec![(0.0, 0.0)],
+                })
+                .collect();
         }
+
+        for (series, value) in self.series.iter_mut().zip(values) {
+            series.data.push((data.counter, value));
+            if series.data.len() > data.config.history {
+                series.data.remove(0);
+            }
+        }
+
+        if matches!(self.graph_type, ChartKind::Cpu | ChartKind::CpuCores) {
+            self.throttling = data.throttling;
+            if data.throttling.is_some() {
+                self.throttled.push((data.counter, 100.0));
+            }
+            let oldest = data.counter - data.config.history as f64;
+            self.throttled.retain(|&(x, _)| x > oldest);
+        }
+    }
+
+    fn current(&self, index: usize) -> f64 {
+        self.series
+            .get(index)
+            .and_then(|series| series.data.last())
+            .map(|&(_, v)| v)
+            .unwrap_or(0.0)
+    }
+
+    fn current_named(&self, name: &str) -> f64 {
+        self.series
+            .iter()
+            .position(|series| series.name == name)
+            .map_or(0.0, |i| self.current(i))
+    }
+
+    fn label(&self) -> String {
+        match self.graph_type {
+            ChartKind::CpuCores => {
+                // Destacar o núcleo mais carregado, que a média esconde
+                let busiest = (0..self.series.len())
+                    .max_by(|&a, &b| self.current(a).total_cmp(&self.current(b)));
+                match busiest {
+                    Some(i) => format!(
+                        "{} (max {:.1}% on {})",
+                        self.title,
+                        self.current(i),
+                        self.series[i].name
+                    ),
+                    None => self.title.clone(),
+                }
+            }
+            ChartKind::DiskIo => {
+                let total = |suffix: &str| -> f64 {
+                    (0..self.series.len())
+                        .filter(|&i| self.series[i].name.ends_with(suffix))
+                        .map(|i| self.current(i))
+                        .sum()
+                };
+                format!(
+                    "{} (R {} W {})",
+                    self.title,
+                    format_rate(total(" read")),
+                    format_rate(total(" write"))
+                )
+            }
+            ChartKind::Network => {
+                let selected = self.selected.as_deref().unwrap_or("total");
+                let rate =
+                    |direction: &str| self.current_named(&format!("{} {}", selected, direction));
+                format!(
+                    "{} - {} (↓ {} ↑ {})",
+                    self.title,
+                    selected,
+                    format_rate(rate("RX")),
+                    format_rate(rate("TX"))
+                )
+            }
+            ChartKind::Gpu => match &self.selected {
+                Some(gpu) => format!(
+                    "{} - {} ({:.1}% mem {:.1}% {:.0}°C)",
+                    self.title,
+                    gpu,
+                    self.current_named(&format!("{} usage", gpu)),
+                    self.current_named(&format!("{} mem", gpu)),
+                    self.current_named(&format!("{} temp", gpu))
+                ),
+                None if self.series.len() > 3 => {
+                    let usage: Vec<f64> = (0..self.series.len())
+                        .step_by(3)
+                        .map(|i| self.current(i))
+                        .collect();
+                    format!(
+                        "{} ({} GPUs, avg {:.1}%)",
+                        self.title,
+                        usage.len(),
+                        usage.iter().sum::<f64>() / usage.len() as f64
+                    )
+                }
+                None => format!("{} ({:.1}%)", self.title, self.current(0)),
+            },
+            ChartKind::Temperature => match &self.selected {
+                Some(name) => format!(
+                    "{} - {} ({:.1}°C)",
+                    self.title,
+                    name,
+                    self.current_named(name)
+                ),
+                None => self.title.clone(),
+            },
+            ChartKind::Discharge => {
+                let total: f64 = (0..self.series.len()).map(|i| self.current(i)).sum();
+                format!("{} ({:.1} W)", self.title, total)
+            }
+            ChartKind::CpuTime => {
+                let cpu = self.selected.as_deref().unwrap_or("all");
+                // As séries são cumulativas: cada estado é a diferença para o anterior
+                let mut below = 0.0;
+                let mut shares = Vec::new();
+                for (state, _) in CpuBreakdown::default().stacked() {
+                    let top = self.current_named(&format!("{} {}", cpu, state));
+                    shares.push((state, top - below));
+                    below = top;
+                }
+                let share = |name: &str| {
+                    shares
+                        .iter()
+                        .find(|(state, _)| *state == name)
+                        .map_or(0.0, |&(_, share)| share)
+                };
+                format!(
+                    "{} - {} (usr {:.0}% sys {:.0}% io {:.0}% st {:.0}%)",
+                    self.title,
+                    cpu,
+                    share("user"),
+                    share("system"),
+                    share("iowait"),
+                    share("steal")
+                )
+            }
+            ChartKind::Pressure => format!(
+                "{} (cpu {:.1}% mem {:.1}% io {:.1}%)",
+                self.title,
+                self.current_named("cpu some"),
+                self.current_named("memory some"),
+                self.current_named("io some")
+            ),
+            ChartKind::Load => format!(
+                "{} ({:.2} {:.2} {:.2})",
+                self.title,
+                self.current(0),
+                self.current(1),
+                self.current(2)
+            ),
+            ChartKind::Frequency => {
+                let clocks: Vec<f64> = (0..self.series.len()).map(|i| self.current(i)).collect();
+                match clocks.iter().copied().reduce(f64::max) {
+                    Some(max) => format!(
+                        "{} (avg {} max {})",
+                        self.title,
+                        cpufreq::format_mhz(clocks.iter().sum::<f64>() / clocks.len() as f64),
+                        cpufreq::format_mhz(max)
+                    ),
+                    None => self.title.clone(),
+                }
+            }
+            ChartKind::Cgroup => match self.series.first() {
+                Some(cpu) => format!(
+                    "{} - {} (CPU {:.1}% mem {:.1}%)",
+                    self.title,
+                    cpu.name.trim_end_matches(" cpu"),
+                    self.current(0),
+                    self.current(1)
+                ),
+                None => self.title.clone(),
+            },
+            // O núcleo já está dentro do pacote, então não entra no total
+            ChartKind::Power => {
+                let total: f64 = ["package", "dram", "gpu"]
+                    .iter()
+                    .map(|name| self.current_named(name))
+                    .sum();
+                format!("{} ({:.1} W)", self.title, total)
+            }
+            _ => format!("{} ({:.1}%)", self.title, self.current(0)),
+        }
+    }
+}
+
+fn format_rate(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_size(bytes_per_sec as u64, BINARY))
+}
+
+/// Eventos por segundo: `850/s`, `12.3k/s`, `1.2M/s`
+fn format_count_rate(per_sec: f64) -> String {
+    if per_sec >= 1e6 {
+        format!("{:.1}M/s", per_sec / 1e6)
+    } else if per_sec >= 1e3 {
+        format!("{:.1}k/s", per_sec / 1e3)
+    } else {
+        format!("{:.0}/s", per_sec)
+    }
+}
+
+/// `3d 4h 12m`
+fn format_uptime(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86400, seconds / 3600 % 24, seconds / 60 % 60);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else {
+        format!("{}h {}m", hours, minutes)
     }
 }
 
@@ -182,81 +867,183 @@ impl Graph {
 struct SystemData {
     cpu_data: Vec<(f64, f64)>,
     memory_data: Vec<(f64, f64)>,
-    gpu_data: Vec<(f64, f64)>,
     counter: f64,
+    timestamp: DateTime<Local>,
+    // Texto extra no cabeçalho das estatísticas (ex.: estado do replay)
+    status: Option<String>,
+    // Sem amostras recentes do agente remoto
+    stale: bool,
     cpu_current: f64,
+    cpu_cores_current: Vec<f64>,
     mem_current: f64,
-    gpu_current: f64,
-    gpu_memory: f64,
-    gpu_temp: f64,
+    gpus: Vec<GpuStats>,
     total_memory: u64,
     used_memory: u64,
     available_memory: u64,
     swap_total: u64,
     swap_used: u64,
-    rx_bytes: u64,
-    tx_bytes: u64,
+    rx_rate: f64,
+    tx_rate: f64,
     rx_bytes_total: u64,
     tx_bytes_total: u64,
-    networks: Vec<String>,
+    network_tracker: NetworkTracker,
+    networks: Vec<InterfaceStats>,
+    process_table: ProcessTable,
+    cgroup_scanner: CgroupScanner,
+    cgroup_table: CgroupTable,
+    disks: Vec<DiskUsage>,
+    disk_io: Vec<DiskIo>,
+    disk_io_tracker: DiskIoTracker,
+    sensors: Vec<Sensor>,
+    power: PowerStatus,
+    rapl: RaplTracker,
+    cpu_power: Option<CpuPower>,
+    energy: SessionEnergy,
+    // Médias de 1, 5 e 15 minutos
+    load: [f64; 3],
+    uptime: u64,
+    boot_time: u64,
+    activity: Option<KernelActivity>,
+    pressure: PressureStats,
+    cgroup_tracker: Option<CgroupTracker>,
+    cgroup: Option<CgroupStats>,
+    cpu_times: Option<CpuBreakdown>,
+    core_times: Vec<CpuBreakdown>,
+    stat_tracker: StatTracker,
+    frequencies: Vec<CoreFrequency>,
+    throttle: ThrottleDetector,
+    throttling: Option<ThrottleReason>,
+    last_slow_refresh: Option<Instant>,
     config: AppConfig,
     system_info: SystemInfo,
     graphs: Vec<Graph>,
+    layout: LayoutNode,
+    alerts: Alerts,
 }
 
 impl SystemData {
-    fn new(config: AppConfig) -> Result<SystemData, Box<dyn std::error::Error>> {
-        let mut graphs = vec![Graph::new(ChartKind::Cpu)];
+    fn new(
+        config: AppConfig,
+        system_info: SystemInfo,
+    ) -> Result<SystemData, Box<dyn std::error::Error>> {
+        let alerts = Alerts::new(&config.alerts)?;
+        let layout = layout::parse(config.layout.as_ref())?;
+        let mut graphs = vec![
+            Graph::new(ChartKind::Cpu),
+            Graph::new(ChartKind::CpuCores),
+            Graph::new(ChartKind::CpuTime),
+            Graph::new(ChartKind::Load),
+        ];
 
         // Adicionar gráficos baseados na configuração
         if !config.no_gpu {
             graphs.push(Graph::new(ChartKind::Gpu));
         }
         graphs.push(Graph::new(ChartKind::Memory));
+        graphs.push(Graph::new(ChartKind::Pressure));
         graphs.push(Graph::new(ChartKind::Swap));
-
-        let system_info = SystemInfo::new()?;
+        if !config.no_network {
+            graphs.push(Graph::new(ChartKind::Network));
+        }
+        if !config.no_disks && cfg!(target_os = "linux") {
+            graphs.push(Graph::new(ChartKind::DiskIo));
+        }
+        graphs.push(Graph::new(ChartKind::Temperature));
+        graphs.push(Graph::new(ChartKind::Discharge));
+        graphs.push(Graph::new(ChartKind::Power));
+        graphs.push(Graph::new(ChartKind::Frequency));
+        graphs.push(Graph::new(ChartKind::Cgroup));
 
         Ok(SystemData {
             cpu_data: vec![(0.0, 0.0)],
             memory_data: vec![(0.0, 0.0)],
-            gpu_data: vec![(0.0, 0.0)],
             counter: 1.0,
+            timestamp: Local::now(),
+            status: None,
+            stale: false,
             cpu_current: 0.0,
+            cpu_cores_current: Vec::new(),
             mem_current: 0.0,
-            gpu_current: 0.0,
-            gpu_memory: 0.0,
-            gpu_temp: 0.0,
+            gpus: Vec::new(),
             total_memory: 0,
             used_memory: 0,
             available_memory: 0,
             swap_total: 0,
             swap_used: 0,
-            rx_bytes: 0,
-            tx_bytes: 0,
+            rx_rate: 0.0,
+            tx_rate: 0.0,
             rx_bytes_total: 0,
             tx_bytes_total: 0,
+            network_tracker: NetworkTracker::default(),
             networks: Vec::new(),
+            process_table: ProcessTable::new(),
+            cgroup_scanner: CgroupScanner::new(&config.sysfs_root),
+            cgroup_table: CgroupTable::new(),
+            disks: Vec::new(),
+            disk_io: Vec::new(),
+            disk_io_tracker: DiskIoTracker::default(),
+            sensors: Vec::new(),
+            power: PowerStatus::default(),
+            rapl: RaplTracker::new(&config.sysfs_root),
+            cpu_power: None,
+            energy: SessionEnergy::default(),
+            load: [0.0; 3],
+            uptime: 0,
+            boot_time: 0,
+            activity: None,
+            pressure: PressureStats::default(),
+            cgroup_tracker: CgroupTracker::new(&config.cgroup, &config.sysfs_root),
+            cgroup: None,
+            cpu_times: None,
+            core_times: Vec::new(),
+            stat_tracker: StatTracker::default(),
+            frequencies: Vec::new(),
+            throttle: ThrottleDetector::default(),
+            throttling: None,
+            last_slow_refresh: None,
             config,
             system_info,
             graphs,
+            layout,
+            alerts,
         })
     }
 
     fn update(
         &mut self,
         sys: &mut System,
-        nvml: &Option<Nvml>,
+        gpus: &mut [Box<dyn GpuBackend>],
     ) -> Result<(), Box<dyn std::error::Error>> {
         sys.refresh_memory();
         sys.refresh_cpu();
+        let load = sys.load_average();
+        self.load = [load.one, load.five, load.fifteen];
+        self.uptime = sys.uptime();
+        self.boot_time = sys.boot_time();
+        let now = Local::now();
+        let elapsed = (now - self.timestamp).to_std().unwrap_or_default();
+        self.timestamp = now;
+
+        // Dentro de um container os totais do sysinfo são do host; os percentuais passam a ser da cota
+        self.cgroup = self
+            .cgroup_tracker
+            .as_mut()
+            .and_then(|tracker| tracker.update(sys.cpus().len()));
 
         // CPU usage
         self.cpu_current = sys.global_cpu_info().cpu_usage() as f64;
+        if let Some(percent) = self.cgroup.as_ref().and_then(|cgroup| cgroup.cpu_percent) {
+            self.cpu_current = percent;
+        }
         self.cpu_data.push((self.counter, self.cpu_current));
         if self.cpu_data.len() > 100 {
             self.cpu_data.remove(0);
         }
+        self.cpu_cores_current = sys
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.cpu_usage() as f64)
+            .collect();
 
         // Atualização detalhada da memória
         self.total_memory = sys.total_memory();
@@ -264,6 +1051,19 @@ impl SystemData {
         self.available_memory = sys.available_memory();
         self.swap_total = sys.total_swap();
         self.swap_used = sys.used_swap();
+        if let Some(cgroup) = &self.cgroup {
+            if let Some(used) = cgroup.memory_used {
+                self.total_memory = cgroup
+                    .memory_max
+                    .map_or(self.total_memory, |max| max.min(self.total_memory));
+                self.used_memory = used.min(self.total_memory);
+                self.available_memory = self.total_memory - self.used_memory;
+            }
+            if let Some(max) = cgroup.swap_max {
+                self.swap_total = max.min(self.swap_total);
+                self.swap_used = cgroup.swap_used.min(self.swap_total);
+            }
+        }
 
         // Memória para o gráfico (mantendo o comportamento suave)
         let target = (self.used_memory as f64 / self.total_memory as f64) * 100.0;
@@ -273,121 +1073,482 @@ impl SystemData {
             self.memory_data.remove(0);
         }
 
-        // GPU update com verificação
+        // Todas as GPUs de todos os backends, numeradas em sequência
         if !self.config.no_gpu {
-            if let Some(nvml) = nvml {
-                if let Ok(device) = nvml.device_by_index(0) {
-                    self.gpu_current = device.utilization_rates()?.gpu as f64;
-                    self.gpu_data.push((self.counter, self.gpu_current));
-                    if self.gpu_data.len() > self.config.history {
-                        self.gpu_data.remove(0);
-                    }
+            self.gpus = gpus
+                .iter_mut()
+                .flat_map(|backend| backend.sample())
+                .collect();
+        }
 
-                    let memory_info = device.memory_info()?;
-                    self.gpu_memory = (memory_info.used as f64 / memory_info.total as f64) * 100.0;
-                    self.gpu_temp = device.temperature(TemperatureSensor::Gpu)? as f64;
-                }
-            }
+        // Energia da sessão: a CPU pelo contador RAPL, as GPUs pelo consumo x tempo
+        if let Some((power, joules)) = self.rapl.sample() {
+            self.cpu_power = Some(power);
+            self.energy.cpu_joules += joules;
+        }
+        if let Some(watts) = self.gpu_power() {
+            self.energy.gpu_joules += watts * elapsed.as_secs_f64();
         }
 
         // Network update com verificação
         if !self.config.no_network {
             sys.refresh_networks();
-            let networks = sys.networks();
+            self.networks = self.network_tracker.update(sys);
 
-            let mut new_rx = 0;
-            let mut new_tx = 0;
+            self.rx_rate = self.networks.iter().map(|i| i.rx_rate).sum();
+            self.tx_rate = self.networks.iter().map(|i| i.tx_rate).sum();
+            self.rx_bytes_total = self.networks.iter().map(|i| i.rx_total).sum();
+            self.tx_bytes_total = self.networks.iter().map(|i| i.tx_total).sum();
+        }
 
-            self.networks.clear();
-            for (interface_name, data) in networks {
-                new_rx += data.received();
-                new_tx += data.transmitted();
+        if !self.config.no_disks {
+            self.disk_io = self.disk_io_tracker.update(&self.config.sysfs_root);
+        }
 
-                self.networks.push(format!(
-                    "{}: ↓{}/s ↑{}/s",
-                    interface_name,
-                    format_size(data.received(), BINARY),
-                    format_size(data.transmitted(), BINARY)
-                ));
+        // Processos e discos são caros de coletar, então atualizam no máximo uma vez por segundo
+        let period = Duration::from_millis(self.config.interval.max(1000));
+        let due = match self.last_slow_refresh {
+            Some(last) => last.elapsed() >= period,
+            None => true,
+        };
+        if due {
+            if !self.config.no_processes {
+                sys.refresh_processes();
+                self.process_table.refresh(sys);
+            }
+            // Percorrer a árvore de cgroups só enquanto o painel ou o gráfico estão abertos
+            if self.config.cgroups || self.cgroup_table.is_drilled() {
+                self.cgroup_table.refresh(self.cgroup_scanner.scan());
+            }
+            if !self.config.no_disks {
+                sys.refresh_disks_list();
+                self.disks = disk::read_disks(sys);
             }
+            if !self.config.no_sensors {
+                self.sensors = sensors::read_sensors(sys, &self.config.sysfs_root);
+                self.select_default_sensor();
+            }
+            self.power = power::read_power_supplies(&self.config.sysfs_root);
+            if let Some(sample) = self.stat_tracker.update() {
+                self.cpu_times = Some(sample.total);
+                self.core_times = sample.cpus;
+                self.activity = Some(sample.activity);
+            }
+            self.pressure = pressure::read_pressure(Path::new("/proc/pressure"));
+            self.frequencies = cpufreq::read_frequencies(&self.config.sysfs_root);
+            self.throttling = self.throttle.update(
+                Instant::now(),
+                cpufreq::read_throttle_count(&self.config.sysfs_root),
+                &self.frequencies,
+                self.cpu_current,
+            );
+            self.last_slow_refresh = Some(Instant::now());
+        }
 
-            self.rx_bytes = new_rx.saturating_sub(self.rx_bytes_total);
-            self.tx_bytes = new_tx.saturating_sub(self.tx_bytes_total);
-            self.rx_bytes_total = new_rx;
-            self.tx_bytes_total = new_tx;
-        }
-
-        // Criar uma struct temporária para passar os dados
-        let update_data = SystemData {
-            counter: self.counter,
-            cpu_current: self.cpu_current,
-            mem_current: self.mem_current,
-            gpu_current: self.gpu_current,
-            swap_total: self.swap_total,
-            swap_used: self.swap_used,
-            config: self.config.clone(),
-            cpu_data: vec![],
-            memory_data: vec![],
-            gpu_data: vec![],
-            gpu_memory: 0.0,
-            gpu_temp: 0.0,
-            total_memory: 0,
-            used_memory: 0,
-            available_memory: 0,
-            rx_bytes: 0,
-            tx_bytes: 0,
-            rx_bytes_total: 0,
-            tx_bytes_total: 0,
-            networks: vec![],
-            system_info: self.system_info.clone(),
-            graphs: vec![],
+        let mut alerts = std::mem::take(&mut self.alerts);
+        alerts.update(&|metric| self.metric_value(metric));
+        self.alerts = alerts;
+
+        self.update_graphs();
+        Ok(())
+    }
+
+    /// Consumo somado das GPUs que informam potência.
+    fn gpu_power(&self) -> Option<f64> {
+        self.gpus
+            .iter()
+            .filter_map(|gpu| gpu.power_watts)
+            .reduce(|a, b| a + b)
+    }
+
+    /// Valor atual de uma métrica usada nas regras de alerta.
+    fn metric_value(&self, metric: &Metric) -> Option<f64> {
+        // Com várias GPUs a regra olha a mais carregada/quente
+        let gpu = |value: fn(&GpuStats) -> Option<f64>| {
+            self.gpus
+                .iter()
+                .filter_map(value)
+                .max_by(|a, b| a.total_cmp(b))
         };
+        match metric {
+            Metric::Cpu => Some(self.cpu_current),
+            Metric::Memory => Some(snapshot::percent(self.used_memory, self.total_memory)),
+            Metric::Swap => Some(snapshot::percent(self.swap_used, self.swap_total)),
+            Metric::Gpu => gpu(|g| Some(g.usage)),
+            Metric::GpuMemory => gpu(|g| Some(g.memory_percent)),
+            Metric::GpuTemp => gpu(|g| g.temperature),
+            Metric::MemoryPressure => self.pressure.memory.map(|memory| memory.some.avg10),
+            Metric::MemoryPressureFull => self
+                .pressure
+                .memory
+                .and_then(|memory| memory.full)
+                .map(|full| full.avg10),
+            Metric::CpuThrottled => self.cgroup.as_ref().map(|cgroup| cgroup.throttled_percent),
+            Metric::Disk(mount) => self
+                .disks
+                .iter()
+                .find(|disk| &disk.mount_point == mount)
+                .map(|disk| disk.percent()),
+        }
+    }
 
-        for graph in &mut self.graphs {
-            graph.update(&update_data);
+    /// O gráfico tem algum alerta ativo sobre o que ele mostra?
+    fn graph_alerting(&self, graph: &Graph) -> bool {
+        self.alerts.any_firing(|metric| {
+            matches!(
+                (&graph.graph_type, metric),
+                (
+                    ChartKind::Cpu | ChartKind::CpuCores,
+                    Metric::Cpu | Metric::CpuThrottled
+                ) | (ChartKind::Memory, Metric::Memory)
+                    | (ChartKind::Swap, Metric::Swap)
+                    | (
+                        ChartKind::Pressure,
+                        Metric::MemoryPressure | Metric::MemoryPressureFull
+                    )
+                    | (
+                        ChartKind::Gpu,
+                        Metric::Gpu | Metric::GpuMemory | Metric::GpuTemp
+                    )
+            )
+        })
+    }
+
+    fn update_graphs(&mut self) {
+        // Os gráficos leem os valores atuais diretamente de `self`
+        let mut graphs = std::mem::take(&mut self.graphs);
+        for graph in &mut graphs {
+            graph.update(self);
         }
+        self.graphs = graphs;
 
         self.counter += 1.0;
-        Ok(())
+    }
+
+    /// Descarta o histórico dos gráficos (usado ao pular para outro ponto do replay).
+    fn reset_graphs(&mut self) {
+        for graph in &mut self.graphs {
+            graph.series.clear();
+        }
+        self.counter = 1.0;
+    }
+
+    /// Aplica uma amostra já coletada, no lugar de ler o sistema.
+    fn apply(&mut self, snapshot: &Snapshot) {
+        self.timestamp = Local
+            .timestamp_millis_opt(snapshot.timestamp_ms)
+            .single()
+            .unwrap_or_else(Local::now);
+
+        self.cpu_current = snapshot.cpu.usage;
+        self.cpu_cores_current = snapshot.cpu.cores.clone();
+
+        self.total_memory = snapshot.memory.total;
+        self.used_memory = snapshot.memory.used;
+        self.available_memory = snapshot.memory.available;
+        self.mem_current = self.mem_current * 0.7 + snapshot.memory.percent * 0.3;
+        self.swap_total = snapshot.swap.total;
+        self.swap_used = snapshot.swap.used;
+
+        self.gpus = snapshot.gpus.clone();
+        self.cpu_power = snapshot.cpu.power;
+        self.frequencies = snapshot
+            .cpu
+            .frequencies_mhz
+            .iter()
+            .enumerate()
+            .map(|(cpu, &current_mhz)| CoreFrequency {
+                cpu: cpu as u32,
+                current_mhz,
+                ..Default::default()
+            })
+            .collect();
+        self.throttling = snapshot.cpu.throttling;
+        self.cpu_times = snapshot.cpu.times;
+        self.pressure = snapshot.pressure.unwrap_or_default();
+        self.cgroup = snapshot.cgroup.clone();
+        if let Some(system) = &snapshot.system {
+            self.load = system.load;
+            self.uptime = system.uptime;
+            self.boot_time = system.boot_time;
+            self.activity = system.activity;
+        }
+        self.core_times = snapshot.cpu.core_times.clone();
+        self.energy = snapshot.energy.unwrap_or_default();
+
+        if let Some(network) = &snapshot.network {
+            self.rx_rate = network.rx_rate;
+            self.tx_rate = network.tx_rate;
+            self.rx_bytes_total = network.rx_total;
+            self.tx_bytes_total = network.tx_total;
+            self.networks = network.interfaces.clone();
+        }
+
+        self.update_graphs();
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            timestamp: self.timestamp.to_rfc3339(),
+            timestamp_ms: self.timestamp.timestamp_millis(),
+            cpu: CpuSnapshot {
+                usage: self.cpu_current,
+                cores: self.cpu_cores_current.clone(),
+                power: self.cpu_power,
+                frequencies_mhz: self
+                    .frequencies
+                    .iter()
+                    .map(|core| core.current_mhz)
+                    .collect(),
+                throttling: self.throttling,
+                times: self.cpu_times,
+                core_times: self.core_times.clone(),
+            },
+            memory: MemorySnapshot {
+                total: self.total_memory,
+                used: self.used_memory,
+                available: self.available_memory,
+                percent: snapshot::percent(self.used_memory, self.total_memory),
+            },
+            swap: MemorySnapshot {
+                total: self.swap_total,
+                used: self.swap_used,
+                available: self.swap_total.saturating_sub(self.swap_used),
+                percent: snapshot::percent(self.swap_used, self.swap_total),
+            },
+            system: Some(SystemSnapshot {
+                load: self.load,
+                uptime: self.uptime,
+                boot_time: self.boot_time,
+                activity: self.activity,
+            }),
+            pressure: (!self.pressure.is_empty()).then_some(self.pressure),
+            cgroup: self.cgroup.clone(),
+            gpus: self.gpus.clone(),
+            energy: (self.cpu_power.is_some() || self.energy.gpu_joules > 0.0)
+                .then_some(self.energy),
+            network: (!self.config.no_network).then(|| NetworkSnapshot {
+                rx_rate: self.rx_rate,
+                tx_rate: self.tx_rate,
+                rx_total: self.rx_bytes_total,
+                tx_total: self.tx_bytes_total,
+                interfaces: self.networks.clone(),
+            }),
+        }
+    }
+
+    /// Alterna o gráfico de rede entre o total e cada interface.
+    fn cycle_network_interface(&mut self) {
+        let mut options = vec!["total".to_string()];
+        options.extend(self.networks.iter().map(|i| i.name.clone()));
+
+        for graph in &mut self.graphs {
+            if graph.graph_type == ChartKind::Network {
+                let next = graph
+                    .selected
+                    .as_ref()
+                    .and_then(|current| options.iter().position(|o| o == current))
+                    .map_or(0, |position| (position + 1) % options.len());
+                graph.selected = Some(options[next].clone());
+            }
+        }
+    }
+
+    /// Alterna o gráfico de tempo de CPU entre o total e cada CPU.
+    fn cycle_cpu_time(&mut self) {
+        let mut options = vec!["all".to_string()];
+        options.extend((0..self.core_times.len()).map(|i| format!("cpu{}", i)));
+
+        for graph in &mut self.graphs {
+            if graph.graph_type == ChartKind::CpuTime {
+                let next = graph
+                    .selected
+                    .as_ref()
+                    .and_then(|current| options.iter().position(|o| o == current))
+                    .map_or(0, |position| (position + 1) % options.len());
+                graph.selected = Some(options[next].clone());
+            }
+        }
+    }
+
+    /// Começa o gráfico de temperatura pelo sensor da CPU (pacote ou Tctl), se houver.
+    fn select_default_sensor(&mut self) {
+        let temperatures: Vec<&Sensor> = self
+            .sensors
+            .iter()
+            .filter(|sensor| sensor.kind == SensorKind::Temperature)
+            .collect();
+        let preferred = temperatures
+            .iter()
+            .find(|sensor| {
+                ["Package", "Tctl", "Tdie"]
+                    .iter()
+                    .any(|label| sensor.label.starts_with(label))
+            })
+            .or(temperatures.first())
+            .map(|sensor| sensor.name());
+
+        for graph in &mut self.graphs {
+            if graph.graph_type == ChartKind::Temperature && graph.selected.is_none() {
+                graph.selected = preferred.clone();
+            }
+        }
+    }
+
+    /// Passa o gráfico de temperatura para o próximo sensor.
+    fn cycle_temperature_sensor(&mut self) {
+        let names: Vec<String> = self
+            .sensors
+            .iter()
+            .filter(|sensor| sensor.kind == SensorKind::Temperature)
+            .map(|sensor| sensor.name())
+            .collect();
+        if names.is_empty() {
+            return;
+        }
+        for graph in &mut self.graphs {
+            if graph.graph_type == ChartKind::Temperature {
+                let next = graph
+                    .selected
+                    .as_ref()
+                    .and_then(|current| names.iter().position(|name| name == current))
+                    .map_or(0, |position| (position + 1) % names.len());
+                graph.selected = Some(names[next].clone());
+            }
+        }
+    }
+
+    /// Alterna o gráfico de GPU entre a visão geral e cada dispositivo.
+    fn cycle_gpu(&mut self) {
+        let count = self.gpus.len();
+        for graph in &mut self.graphs {
+            if graph.graph_type == ChartKind::Gpu {
+                let next = match graph.selected.as_deref() {
+                    None => 0,
+                    Some(current) => current
+                        .strip_prefix("gpu")
+                        .and_then(|i| i.parse::<usize>().ok())
+                        .map_or(count, |i| i + 1),
+                };
+                graph.selected = (next < count).then(|| format!("gpu{}", next));
+            }
+        }
+    }
+
+    /// O gráfico visível de um tipo; `Cpu` também encontra o gráfico por núcleo.
+    fn chart(&self, kind: &ChartKind) -> Option<&Graph> {
+        self.visible_graphs().find(|graph| {
+            graph.graph_type == *kind
+                || (*kind == ChartKind::Cpu && graph.graph_type == ChartKind::CpuCores)
+        })
+    }
+
+    /// Gráficos exibidos na tela; CPU agregada e por núcleo se alternam.
+    fn visible_graphs(&self) -> impl Iterator<Item = &Graph> {
+        self.graphs
+            .iter()
+            .filter(move |graph| match graph.graph_type {
+                ChartKind::Cpu => !self.config.per_core,
+                ChartKind::CpuCores => self.config.per_core,
+                // Só aparece se houver algum sensor de temperatura
+                ChartKind::Temperature => !self.config.no_sensors && !graph.series.is_empty(),
+                // Só em máquinas com bateria
+                ChartKind::Discharge => !self.power.batteries.is_empty(),
+                // Só com RAPL legível ou GPU que informe consumo
+                ChartKind::Power => !self.config.no_power && !graph.series.is_empty(),
+                // Só com cpufreq disponível
+                ChartKind::Frequency => !graph.series.is_empty(),
+                // Só no Linux, a partir da segunda leitura de /proc/stat
+                ChartKind::CpuTime => !graph.series.is_empty(),
+                // O Windows não tem load average
+                ChartKind::Load => !cfg!(target_os = "windows"),
+                // Só em kernels com PSI
+                ChartKind::Pressure => !graph.series.is_empty(),
+                // Só depois de abrir um cgroup com Enter no painel
+                ChartKind::Cgroup => !graph.series.is_empty(),
+                _ => true,
+            })
     }
 }
 
-fn draw_chart<'a>(graph: &'a Graph, counter: f64) -> Chart<'a> {
-    let current_value = graph.data.last().map(|&(_, v)| v).unwrap_or(0.0);
+fn draw_chart(graph: &Graph, counter: f64, alerting: bool, stale: bool) -> Chart<'_> {
+    // Borda em vermelho enquanto houver um alerta ativo, apagada com dados antigos
+    let border = if alerting {
+        Color::Red
+    } else if stale {
+        Color::DarkGray
+    } else {
+        graph.color
+    };
 
-    let dataset = Dataset::default()
-        .name(&graph.title)
-        .marker(symbols::Marker::Braille)
-        .graph_type(GraphType::Line)
-        .style(Style::default().fg(graph.color.clone()))
-        .data(&graph.data);
+    let mut datasets: Vec<Dataset> = graph
+        .visible_series()
+        .map(|series| {
+            Dataset::default()
+                .name(series.name.as_str())
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(series.color))
+                .data(&series.data)
+        })
+        .collect();
+    // Momentos de throttling marcados no topo do gráfico de CPU
+    if !graph.throttled.is_empty() {
+        datasets.push(
+            Dataset::default()
+                .name("throttled")
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(Color::Red))
+                .data(&graph.throttled),
+        );
+    }
 
-    Chart::new(vec![dataset])
+    let mut title = vec![Span::styled(
+        graph.label(),
+        Style::default().fg(border).add_modifier(Modifier::BOLD),
+    )];
+    if let Some(reason) = graph.throttling {
+        title.push(Span::styled(
+            format!(" THROTTLED ({})", reason),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if stale {
+        title.push(Span::styled(
+            " STALE",
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    Chart::new(datasets)
         .block(
             Block::default()
-                .title(Span::styled(
-                    format!("{} ({:.1}%)", graph.title, current_value),
-                    Style::default()
-                        .fg(graph.color.clone())
-                        .add_modifier(Modifier::BOLD),
-                ))
+                .title(title)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(graph.color.clone())),
+                .border_style(Style::default().fg(border)),
         )
         .x_axis(
             tui::widgets::Axis::default()
                 .style(Style::default().fg(Color::Gray))
                 .bounds([counter - 100.0, counter]),
         )
-        .y_axis(
-            tui::widgets::Axis::default()
+        .y_axis({
+            let y_max = graph.y_max();
+            let axis = tui::widgets::Axis::default()
                 .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, 100.0]),
-        )
+                .bounds([0.0, y_max]);
+            match graph.y_label(y_max) {
+                Some(label) => axis.labels(vec![Span::raw("0"), Span::raw(label)]),
+                None => axis,
+            }
+        })
 }
 
-fn draw_stats(data: &SystemData) -> Paragraph {
-    let time = Local::now().format("%H:%M:%S").to_string();
+fn draw_stats(data: &SystemData) -> Paragraph<'_> {
+    let time = data.timestamp.format("%H:%M:%S").to_string();
     let mut text = vec![
         // Cabeçalho com OS colorido
         Spans::from(vec![Span::styled(
@@ -399,6 +1560,15 @@ fn draw_stats(data: &SystemData) -> Paragraph {
         Spans::from(vec![
             Span::styled("System Status ", Style::default().fg(Color::White)),
             Span::styled(time, Style::default().fg(Color::Cyan)),
+            Span::styled(
+                data.status
+                    .as_ref()
+                    .map(|status| format!(" {}", status))
+                    .unwrap_or_default(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
         ]),
         Spans::from(""),
         // CPU Info
@@ -506,45 +1676,452 @@ fn draw_stats(data: &SystemData) -> Paragraph {
         ]),
     ];
 
-    // GPU Info (condicional)
-    if !data.config.no_gpu {
-        text.extend_from_slice(&[
-            Spans::from(""),
-            Spans::from(vec![Span::styled(
-                "GPU",
+    // Detalhes da CPU entram antes da linha de núcleos, que fecha a árvore
+    let cyan = Style::default().fg(Color::Cyan);
+    let mut cpu_lines = Vec::new();
+
+    // Divisão do tempo de /proc/stat; iowait e steal altos em amarelo
+    if let Some(times) = data.cpu_times {
+        let share = |name: &'static str, value: f64, warn: bool| {
+            let style = if warn && value >= 10.0 {
                 Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )]),
-            Spans::from(vec![
-                Span::raw("├─ Model:       "),
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                cyan
+            };
+            Span::styled(format!("{} {:.1}% ", name, value), style)
+        };
+        cpu_lines.push(Spans::from(vec![
+            Span::raw("├─ Time:   "),
+            share("usr", times.user, false),
+            share("sys", times.system, false),
+            share("nice", times.nice, false),
+        ]));
+        cpu_lines.push(Spans::from(vec![
+            Span::raw("│          "),
+            share("io", times.iowait, true),
+            share("irq", times.irq, false),
+            share("sirq", times.softirq, false),
+            share("st", times.steal, true),
+            share("gst", times.guest, false),
+        ]));
+    }
+
+    // Clock e throttling
+    if !data.frequencies.is_empty() {
+        let average = data
+            .frequencies
+            .iter()
+            .map(|core| core.current_mhz)
+            .sum::<f64>()
+            / data.frequencies.len() as f64;
+        let first = &data.frequencies[0];
+        let mut detail = Vec::new();
+        if let (Some(min), Some(max)) = (first.min_mhz, first.max_mhz) {
+            detail.push(format!(
+                "{}-{}",
+                cpufreq::format_mhz(min),
+                cpufreq::format_mhz(max)
+            ));
+        }
+        if let Some(governor) = &first.governor {
+            detail.push(governor.clone());
+        }
+        let detail = if detail.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", detail.join(", "))
+        };
+        cpu_lines.push(Spans::from(vec![
+            Span::raw("├─ Clock:  "),
+            Span::styled(format!("{}{}", cpufreq::format_mhz(average), detail), cyan),
+        ]));
+        if let Some(reason) = data.throttling {
+            cpu_lines.push(Spans::from(vec![
+                Span::raw("├─ "),
                 Span::styled(
-                    &data.system_info.gpu_model,
-                    Style::default().fg(Color::Green),
+                    format!("Throttling ({})", reason),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                 ),
-            ]),
-            Spans::from(vec![
-                Span::raw("├─ Usage:       "),
+            ]));
+        }
+    }
+
+    // Consumo pelo RAPL
+    if !data.config.no_power {
+        if let Some(power) = data.cpu_power {
+            let mut parts = Vec::new();
+            if let Some(core) = power.core {
+                parts.push(format!("core {:.1} W", core));
+            }
+            if let Some(dram) = power.dram {
+                parts.push(format!("DRAM {:.1} W", dram));
+            }
+            let detail = if parts.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", parts.join(", "))
+            };
+            cpu_lines.push(Spans::from(vec![
+                Span::raw("├─ Power:  "),
+                Span::styled(format!("{:>5.1} W{}", power.package, detail), cyan),
+            ]));
+        }
+        if data.cpu_power.is_some() || data.energy.gpu_joules > 0.0 {
+            // Total da sessão; com GPU inclui o consumo dela
+            let source = match (data.cpu_power.is_some(), data.energy.gpu_joules > 0.0) {
+                (true, true) => " CPU+GPU",
+                (false, true) => " GPU",
+                _ => "",
+            };
+            cpu_lines.push(Spans::from(vec![
+                Span::raw("├─ Energy: "),
                 Span::styled(
-                    format!("{:>5.1}%", data.gpu_current),
-                    Style::default().fg(Color::Green),
+                    format!(
+                        "{}{}",
+                        rapl::format_energy(data.energy.total_joules()),
+                        source
+                    ),
+                    cyan,
                 ),
-            ]),
-            Spans::from(vec![
-                Span::raw("├─ Memory:      "),
+            ]));
+        }
+    }
+    text.splice(6..6, cpu_lines);
+
+    // Alertas ativos logo abaixo do cabeçalho
+    let firing: Vec<_> = data.alerts.firing().collect();
+    if !firing.is_empty() {
+        let mut lines = vec![Spans::from(vec![Span::styled(
+            "Alerts",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )])];
+        for (i, alert) in firing.iter().enumerate() {
+            lines.push(Spans::from(vec![
+                Span::raw(if i == firing.len() - 1 {
+                    "└─ "
+                } else {
+                    "├─ "
+                }),
                 Span::styled(
-                    format!("{:>5.1}%", data.gpu_memory),
-                    Style::default().fg(Color::Green),
+                    format!(
+                        "{} ({:.1})",
+                        alert.config.rule,
+                        alert.value.unwrap_or_default()
+                    ),
+                    Style::default().fg(Color::Red),
                 ),
-            ]),
-            Spans::from(vec![
-                Span::raw("└─ Temperature: "),
+            ]));
+        }
+        lines.push(Spans::from(""));
+        text.splice(3..3, lines);
+    }
+
+    // Uptime, load e atividade do kernel logo abaixo da linha de status
+    let gray = Style::default().fg(Color::Gray);
+    let white = Style::default().fg(Color::White);
+    let mut header = vec![Spans::from(vec![
+        Span::styled("Up ", gray),
+        Span::styled(format_uptime(data.uptime), white),
+        Span::styled(
+            Local
+                .timestamp_opt(data.boot_time as i64, 0)
+                .single()
+                .filter(|_| data.boot_time > 0)
+                .map(|boot| format!(" (since {})", boot.format("%Y-%m-%d %H:%M")))
+                .unwrap_or_default(),
+            gray,
+        ),
+        Span::styled("  Load ", gray),
+        Span::styled(
+            format!(
+                "{:.2} {:.2} {:.2}",
+                data.load[0], data.load[1], data.load[2]
+            ),
+            Style::default().fg(Color::LightBlue),
+        ),
+    ])];
+    if let Some(activity) = data.activity {
+        let blocked = if activity.blocked > 0 {
+            Style::default().fg(Color::Yellow)
+        } else {
+            white
+        };
+        header.push(Spans::from(vec![
+            Span::styled("Tasks ", gray),
+            Span::styled(format!("{} running", activity.running), white),
+            Span::styled(", ", gray),
+            Span::styled(format!("{} blocked", activity.blocked), blocked),
+            Span::styled(format!(", {} total", activity.total), gray),
+        ]));
+        header.push(Spans::from(vec![
+            Span::styled("ctxt ", gray),
+            Span::styled(format_count_rate(activity.context_switches), white),
+            Span::styled("  intr ", gray),
+            Span::styled(format_count_rate(activity.interrupts), white),
+            Span::styled("  forks ", gray),
+            Span::styled(format_count_rate(activity.forks), white),
+        ]));
+    }
+    text.splice(2..2, header);
+
+    if let Some(cgroup) = &data.cgroup {
+        text.push(Spans::from(""));
+        text.push(Spans::from(vec![
+            Span::styled(
+                "Cgroup ",
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(cgroup.path.clone()),
+        ]));
+        if let Some(max) = cgroup.memory_max {
+            text.push(Spans::from(vec![
+                Span::raw("├─ Memory:    "),
                 Span::styled(
-                    format!("{:>5.1}°C", data.gpu_temp),
-                    Style::default().fg(Color::Green),
+                    format!(
+                        "{} / {}",
+                        format_size(cgroup.memory_used.unwrap_or(0), BINARY),
+                        format_size(max, BINARY)
+                    ),
+                    Style::default().fg(Color::Yellow),
                 ),
-            ]),
-        ]);
+            ]));
+        }
+        if let Some(quota) = cgroup.cpu_quota {
+            text.push(Spans::from(vec![
+                Span::raw("├─ CPU quota: "),
+                Span::styled(
+                    format!("{:.2} cores", quota),
+                    Style::default().fg(Color::Cyan),
+                ),
+            ]));
+        }
+        if !cgroup.has_limits() {
+            text.push(Spans::from("├─ No memory or CPU limit"));
+        }
+        text.push(Spans::from(vec![
+            Span::raw("└─ Throttled: "),
+            Span::styled(
+                format!("{:>5.1}%", cgroup.throttled_percent),
+                Style::default().fg(if cgroup.throttled_percent > 0.0 {
+                    Color::Red
+                } else {
+                    Color::Green
+                }),
+            ),
+            Span::raw(format!(
+                " of periods ({} periods, {:.1} s total)",
+                cgroup.throttled_periods, cgroup.throttled_seconds
+            )),
+        ]));
+    }
+
+    // PSI: verde até 5%, amarelo até 20%, vermelho acima
+    if !data.pressure.is_empty() {
+        let level = |avg10: f64| {
+            Style::default().fg(if avg10 >= 20.0 {
+                Color::Red
+            } else if avg10 >= 5.0 {
+                Color::Yellow
+            } else {
+                Color::Green
+            })
+        };
+        text.push(Spans::from(""));
+        text.push(Spans::from(vec![Span::styled(
+            "Pressure",
+            Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        let resources: Vec<_> = data
+            .pressure
+            .resources()
+            .into_iter()
+            .filter_map(|(name, pressure)| Some((name, pressure?)))
+            .collect();
+        for (i, (name, pressure)) in resources.iter().enumerate() {
+            let mut line = vec![
+                Span::raw(if i == resources.len() - 1 {
+                    "└─ "
+                } else {
+                    "├─ "
+                }),
+                Span::raw(format!("{:<7} ", format!("{}:", name))),
+                Span::styled("●", level(pressure.some.avg10)),
+                Span::raw(format!(
+                    " some {:>5.1}% {:>5.1}% {:>5.1}%",
+                    pressure.some.avg10, pressure.some.avg60, pressure.some.avg300
+                )),
+            ];
+            if let Some(full) = pressure.full {
+                line.push(Span::styled(
+                    format!("  full {:>5.1}%", full.avg10),
+                    level(full.avg10),
+                ));
+            }
+            text.push(Spans::from(line));
+        }
+    }
+
+    // GPU Info (condicional): uma seção por placa, ou uma linha por placa quando são muitas
+    if !data.config.no_gpu {
+        let green = Style::default().fg(Color::Green);
+        let temperature = |gpu: &GpuStats| {
+            gpu.temperature
+                .map_or("-".to_string(), |t| format!("{:.1}°C", t))
+        };
+        if data.gpus.len() <= 2 {
+            for (i, gpu) in data.gpus.iter().enumerate() {
+                let memory = if gpu.memory_total > 0 {
+                    format!(
+                        "{:>5.1}% ({} / {})",
+                        gpu.memory_percent,
+                        format_size(gpu.memory_used, BINARY),
+                        format_size(gpu.memory_total, BINARY)
+                    )
+                } else {
+                    format!("{:>5.1}%", gpu.memory_percent)
+                };
+                text.extend_from_slice(&[
+                    Spans::from(""),
+                    Spans::from(vec![Span::styled(
+                        if data.gpus.len() == 1 {
+                            "GPU".to_string()
+                        } else {
+                            format!("GPU {}", i)
+                        },
+                        green.add_modifier(Modifier::BOLD),
+                    )]),
+                    Spans::from(vec![
+                        Span::raw("├─ Model:       "),
+                        Span::styled(&gpu.model, green),
+                    ]),
+                    Spans::from(vec![
+                        Span::raw("├─ Usage:       "),
+                        Span::styled(format!("{:>5.1}%", gpu.usage), green),
+                    ]),
+                    Spans::from(vec![
+                        Span::raw("├─ Memory:      "),
+                        Span::styled(memory, green),
+                    ]),
+                    Spans::from(vec![
+                        Span::raw("└─ Temperature: "),
+                        Span::styled(format!("{:>6}", temperature(gpu)), green),
+                    ]),
+                ]);
+                if let (Some(watts), false) = (gpu.power_watts, data.config.no_power) {
+                    text.insert(
+                        text.len() - 1,
+                        Spans::from(vec![
+                            Span::raw("├─ Power:       "),
+                            Span::styled(format!("{:>5.1} W", watts), green),
+                        ]),
+                    );
+                }
+                if let Some(frequency) = gpu.frequency_mhz {
+                    let clock = match gpu.max_frequency_mhz {
+                        Some(max) => format!("{:.0} / {:.0} MHz", frequency, max),
+                        None => format!("{:.0} MHz", frequency),
+                    };
+                    // Entra antes da temperatura, que fecha a árvore
+                    text.insert(
+                        text.len() - 1,
+                        Spans::from(vec![
+                            Span::raw("├─ Clock:       "),
+                            Span::styled(clock, green),
+                        ]),
+                    );
+                }
+            }
+        } else {
+            text.push(Spans::from(""));
+            text.push(Spans::from(vec![Span::styled(
+                format!("GPUs ({})", data.gpus.len()),
+                green.add_modifier(Modifier::BOLD),
+            )]));
+            for (i, gpu) in data.gpus.iter().enumerate() {
+                text.push(Spans::from(vec![
+                    Span::raw(if i == data.gpus.len() - 1 {
+                        "└─ "
+                    } else {
+                        "├─ "
+                    }),
+                    Span::styled(
+                        format!(
+                            "gpu{}: {:>5.1}% mem {:>5.1}% {:>6}",
+                            i,
+                            gpu.usage,
+                            gpu.memory_percent,
+                            temperature(gpu)
+                        ),
+                        green,
+                    ),
+                ]));
+            }
+        }
+    }
+
+    // Bateria e fonte (só aparece em máquinas com bateria)
+    if !data.power.batteries.is_empty() {
+        let yellow = Style::default().fg(Color::LightYellow);
+        text.push(Spans::from(""));
+        text.push(Spans::from(vec![Span::styled(
+            match data.power.ac_online {
+                Some(true) => "Power (AC)",
+                Some(false) => "Power (battery)",
+                None => "Power",
+            },
+            yellow.add_modifier(Modifier::BOLD),
+        )]));
+        for (i, battery) in data.power.batteries.iter().enumerate() {
+            let last = i == data.power.batteries.len() - 1;
+            let (branch, indent) = if last {
+                ("└─ ", "   ")
+            } else {
+                ("├─ ", "│  ")
+            };
+            let charge_style = if battery.percent <= 10.0 && battery.is_discharging() {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                yellow
+            };
+            text.push(Spans::from(vec![
+                Span::raw(format!("{}{}: ", branch, battery.name)),
+                Span::styled(format!("{:.0}%", battery.percent), charge_style),
+                Span::styled(format!(" {}", battery.state), yellow),
+            ]));
+
+            let mut details = Vec::new();
+            if let Some(watts) = battery.watts {
+                details.push(("Power:  ", format!("{:.1} W", watts)));
+            }
+            if let Some(time_left) = battery.time_left {
+                let label = if battery.is_charging() {
+                    "Full in:"
+                } else {
+                    "Empty in:"
+                };
+                details.push((label, power::format_duration(time_left)));
+            }
+            if let Some(health) = battery.health {
+                details.push(("Health: ", format!("{:.0}%", health)));
+            }
+            for (j, (label, value)) in details.iter().enumerate() {
+                let branch = if j == details.len() - 1 {
+                    "└─ "
+                } else {
+                    "├─ "
+                };
+                text.push(Spans::from(vec![
+                    Span::raw(format!("{}{}{} ", indent, branch, label)),
+                    Span::styled(value.clone(), yellow),
+                ]));
+            }
+        }
     }
 
     // Network Info (condicional)
@@ -559,22 +2136,30 @@ fn draw_stats(data: &SystemData) -> Paragraph {
             )]),
             Spans::from(vec![
                 Span::raw("├─ Download: "),
+                Span::styled(format_rate(data.rx_rate), Style::default().fg(Color::Blue)),
+            ]),
+            Spans::from(vec![
+                Span::raw("├─ Upload:   "),
+                Span::styled(format_rate(data.tx_rate), Style::default().fg(Color::Blue)),
+            ]),
+            Spans::from(vec![
+                Span::raw("├─ Received: "),
                 Span::styled(
-                    format!("{}/s", format_size(data.rx_bytes, BINARY)),
+                    format_size(data.rx_bytes_total, BINARY),
                     Style::default().fg(Color::Blue),
                 ),
             ]),
             Spans::from(vec![
-                Span::raw("└─ Upload:   "),
+                Span::raw("└─ Sent:     "),
                 Span::styled(
-                    format!("{}/s", format_size(data.tx_bytes, BINARY)),
+                    format_size(data.tx_bytes_total, BINARY),
                     Style::default().fg(Color::Blue),
                 ),
             ]),
         ]);
 
         // Interfaces de rede
-        for (i, network_info) in data.networks.iter().enumerate() {
+        for (i, interface) in data.networks.iter().enumerate() {
             let is_last = i == data.networks.len() - 1;
             text.push(Spans::from(vec![
                 Span::raw(if is_last {
@@ -582,7 +2167,15 @@ fn draw_stats(data: &SystemData) -> Paragraph {
                 } else {
                     "    ├─ "
                 }),
-                Span::styled(network_info, Style::default().fg(Color::Blue)),
+                Span::styled(
+                    format!(
+                        "{}: ↓{} ↑{}",
+                        interface.name,
+                        format_rate(interface.rx_rate),
+                        format_rate(interface.tx_rate)
+                    ),
+                    Style::default().fg(Color::Blue),
+                ),
             ]));
         }
     }
@@ -597,107 +2190,594 @@ fn draw_stats(data: &SystemData) -> Paragraph {
         .wrap(Wrap { trim: true })
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
-    let mut config = AppConfig::load();
+/// Coleta sem TUI, escrevendo uma linha JSON por amostra em stdout (`--format json`)
+/// ou apenas gravando/exportando (`--format none`).
+fn run_headless(
+    mut config: AppConfig,
+    system_info: SystemInfo,
+    mut gpus: Vec<Box<dyn GpuBackend>>,
+    format: OutputFormat,
+    mut recorder: Option<Recorder>,
+    metrics: Option<MetricsServer>,
+    agent: Option<AgentServer>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Processos, discos e sensores não fazem parte da amostra, então não são coletados
+    config.no_processes = true;
+    config.no_disks = true;
+    config.no_sensors = true;
 
-    // Lidar com reset de configuração
-    if args.reset_config {
-        config = AppConfig::default();
-        config.save()?;
-        println!("Configuration reset to defaults.");
-        return Ok(());
-    }
+    let interval = Duration::from_millis(config.interval);
+    let mut data = SystemData::new(config, system_info)?;
+    // ...a não ser que um alerta `disk:` dependa deles
+    data.config.no_disks = !data
+        .alerts
+        .watches(|metric| matches!(metric, Metric::Disk(_)));
+    let mut sys = System::new_all();
 
-    // Atualizar configuração com argumentos da linha de comando
-    config.update_from_args(&args);
+    // Primeira leitura só para ter uma base de CPU e rede; com intervalos longos a primeira
+    // amostra sai depois de 1 s, para que `--connect` não espere um intervalo inteiro
+    data.update(&mut sys, &mut gpus)?;
+    std::thread::sleep(
+        interval
+            .min(Duration::from_secs(1))
+            .max(System::MINIMUM_CPU_UPDATE_INTERVAL),
+    );
 
-    // Salvar configuração se solicitado
-    if args.save_config {
-        config.save()?;
-        println!("Configuration saved as default.");
-        return Ok(());
-    }
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    loop {
+        let started = Instant::now();
+        if let Err(e) = data.update(&mut sys, &mut gpus) {
+            eprintln!("Error updating data: {}", e);
+        }
 
-    // Initialize NVML conditionally - skip on macOS
-    let nvml = if !config.no_gpu && !cfg!(target_os = "macos") {
-        match Nvml::init() {
-            Ok(nvml) => Some(nvml),
-            Err(_) => None,
+        let snapshot = data.snapshot();
+        export(&mut recorder, &metrics, &snapshot);
+        if let Some(agent) = &agent {
+            agent.publish(&snapshot);
+        }
+        if format == OutputFormat::Json {
+            let line = serde_json::to_string(&snapshot)?;
+            if writeln!(out, "{}", line).and_then(|_| out.flush()).is_err() {
+                // stdout fechado (ex.: `| head`), encerrar sem erro
+                return Ok(());
+            }
         }
-    } else {
-        None
-    };
 
-    // Terminal setup
+        std::thread::sleep(interval.saturating_sub(started.elapsed()));
+    }
+}
+
+/// Por que a interface terminou: `q` ou Ctrl+C.
+#[derive(PartialEq)]
+enum Exit {
+    Quit,
+    Interrupt,
+}
+
+/// De onde a interface tira as amostras e para onde as envia.
+struct Sources<'a> {
+    sys: System,
+    gpus: Vec<Box<dyn GpuBackend>>,
+    replay: Option<Replay>,
+    remote: Option<&'a mut RemoteClient>,
+    recorder: Option<Recorder>,
+    metrics: Option<MetricsServer>,
+}
+
+type Term = Terminal<CrosstermBackend<io::Stdout>>;
+
+fn enter_terminal() -> io::Result<Term> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
     let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    Terminal::new(backend)
+}
 
-    // Create app and system info
-    let mut data = SystemData::new(config)?;
-    let mut sys = System::new_all();
+fn leave_terminal(terminal: &mut Term) -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()
+}
+
+/// Grava e exporta uma amostra, coletada aqui ou recebida de um agente.
+fn export(recorder: &mut Option<Recorder>, metrics: &Option<MetricsServer>, snapshot: &Snapshot) {
+    if let Some(rec) = recorder.as_mut() {
+        if let Err(e) = rec.record(snapshot) {
+            eprintln!("Error recording sample: {}", e);
+        }
+    }
+    if let Some(metrics) = metrics {
+        metrics.publish(snapshot);
+    }
+}
 
-    let mut last_update = std::time::Instant::now();
+/// A interface de um host: coleta local, replay ou as amostras de um agente.
+fn run_tui(
+    terminal: &mut Term,
+    data: &mut SystemData,
+    sources: Sources,
+) -> Result<Exit, Box<dyn std::error::Error>> {
+    let Sources {
+        mut sys,
+        mut gpus,
+        mut replay,
+        mut remote,
+        mut recorder,
+        metrics,
+    } = sources;
+    let mut last_update = Instant::now();
 
     loop {
         // Só atualiza os dados se o intervalo configurado passou
-        if last_update.elapsed() >= Duration::from_millis(data.config.interval) {
-            if let Err(e) = data.update(&mut sys, &nvml) {
+        if let Some(replay) = replay.as_mut() {
+            replay.tick(data);
+        } else if let Some(remote) = remote.as_mut() {
+            for sample in remote.poll() {
+                data.apply(&sample);
+                export(&mut recorder, &metrics, &sample);
+            }
+            // Depois de uma reconexão o agente pode ter sido reiniciado em outro hardware
+            if let Some(system) = remote.take_system() {
+                data.system_info = system;
+            }
+            data.status = Some(remote.status());
+            data.stale = remote.is_stale();
+        } else if last_update.elapsed() >= Duration::from_millis(data.config.interval) {
+            if let Err(e) = data.update(&mut sys, &mut gpus) {
                 eprintln!("Error updating data: {}", e);
+            } else if recorder.is_some() || metrics.is_some() {
+                export(&mut recorder, &metrics, &data.snapshot());
             }
-            last_update = std::time::Instant::now();
+            last_update = Instant::now();
         }
 
         terminal.draw(|f| {
-            let size = f.size();
-            let chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .margin(1)
-                .constraints([Constraint::Percentage(80), Constraint::Percentage(20)].as_ref())
-                .split(size);
+            let area = f.size().inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+            // O painel de cgroups ocupa o lugar da tabela de processos
+            let show_table = !data.config.no_processes || data.config.cgroups;
+            // Gráficos sem lugar próprio no layout ficam empilhados em `charts`
+            let placed_charts = data.layout.placed_charts();
+            let stacked: Vec<&Graph> = data
+                .visible_graphs()
+                .filter(|graph| !placed_charts.contains(&graph.graph_type))
+                .collect();
+            let visible = |widget: &Widget| match widget {
+                Widget::Chart(kind) => data.chart(kind).is_some(),
+                Widget::Charts => !stacked.is_empty(),
+                Widget::Stats => true,
+                Widget::Processes => show_table,
+                Widget::Disks => !data.config.no_disks,
+                Widget::Sensors => !data.config.no_sensors,
+            };
+            let auto = |widget: &Widget| match widget {
+                Widget::Disks => Some(data.disks.len() as u16 + 3),
+                Widget::Sensors => Some(sensors::panel_rows(&data.sensors) as u16 + 3),
+                _ => None,
+            };
+            let placed = data.layout.place(area, &visible, &auto);
 
-            // Criar layout para os gráficos
-            let n_graphs = data.graphs.len();
-            let constraints: Vec<Constraint> =
-                vec![Constraint::Percentage(100 / n_graphs as u16); n_graphs];
-
-            let charts = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(constraints)
-                .split(chunks[0]);
-
-            // Renderizar todos os gráficos
-            for (i, graph) in data.graphs.iter().enumerate() {
-                f.render_widget(draw_chart(graph, data.counter), charts[i]);
+            for (widget, area) in &placed {
+                match widget {
+                    Widget::Chart(kind) => {
+                        if let Some(graph) = data.chart(kind) {
+                            f.render_widget(
+                                draw_chart(
+                                    graph,
+                                    data.counter,
+                                    data.graph_alerting(graph),
+                                    data.stale,
+                                ),
+                                *area,
+                            );
+                        }
+                    }
+                    Widget::Charts => {
+                        let sizes = vec![(Size::Fill, None); stacked.len()];
+                        for (graph, area) in stacked.iter().zip(layout::split(*area, false, &sizes))
+                        {
+                            f.render_widget(
+                                draw_chart(
+                                    graph,
+                                    data.counter,
+                                    data.graph_alerting(graph),
+                                    data.stale,
+                                ),
+                                area,
+                            );
+                        }
+                    }
+                    Widget::Stats => f.render_widget(draw_stats(data), *area),
+                    Widget::Disks => {
+                        let alerting = data
+                            .alerts
+                            .any_firing(|metric| matches!(metric, Metric::Disk(_)));
+                        f.render_widget(disk::draw_disks(&data.disks, alerting), *area);
+                    }
+                    Widget::Sensors => f.render_widget(sensors::draw_sensors(&data.sensors), *area),
+                    // As tabelas guardam estado e são desenhadas depois
+                    Widget::Processes => {}
+                }
             }
 
-            // Render stats
-            f.render_widget(draw_stats(&data), chunks[1]);
+            if let Some((_, area)) = placed
+                .iter()
+                .find(|(widget, _)| *widget == Widget::Processes)
+            {
+                if data.config.cgroups {
+                    f.render_stateful_widget(
+                        data.cgroup_table.widget(),
+                        *area,
+                        &mut data.cgroup_table.state,
+                    );
+                } else {
+                    f.render_stateful_widget(
+                        data.process_table.widget(),
+                        *area,
+                        &mut data.process_table.state,
+                    );
+                }
+            }
         })?;
 
         // Polling de eventos com timeout curto
         if event::poll(Duration::from_millis(10))? {
             match event::read()? {
-                Event::Key(key) => match key.code {
-                    KeyCode::Char('q') => break,
-                    KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                        break
+                Event::Key(key) => {
+                    if key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(event::KeyModifiers::CONTROL)
+                    {
+                        return Ok(Exit::Interrupt);
+                    }
+                    // No replay, as teclas de reprodução vêm antes de tudo
+                    if let Some(replay) = replay.as_mut() {
+                        if replay.handle_key(key, data) {
+                            continue;
+                        }
+                    }
+                    // A tabela visível tem prioridade (filtro, confirmação, navegação)
+                    if data.config.cgroups {
+                        if data.cgroup_table.handle_key(key) {
+                            continue;
+                        }
+                    } else if !data.config.no_processes && data.process_table.handle_key(key, &sys)
+                    {
+                        continue;
                     }
-                    _ => {}
-                },
+                    match key.code {
+                        KeyCode::Char('q') => return Ok(Exit::Quit),
+                        KeyCode::Char('c') => data.config.per_core = !data.config.per_core,
+                        KeyCode::Char('d') => {
+                            data.config.no_disks = !data.config.no_disks;
+                            data.last_slow_refresh = None;
+                        }
+                        KeyCode::Char('n') => data.cycle_network_interface(),
+                        KeyCode::Char('g') => data.cycle_gpu(),
+                        KeyCode::Char('h') => data.cycle_temperature_sensor(),
+                        KeyCode::Char('b') => data.cycle_cpu_time(),
+                        KeyCode::Char('w') => data.config.no_power = !data.config.no_power,
+                        KeyCode::Char('s') => {
+                            data.config.no_sensors = !data.config.no_sensors;
+                            data.last_slow_refresh = None;
+                        }
+                        KeyCode::Char('p') => {
+                            data.config.no_processes = !data.config.no_processes;
+                            data.last_slow_refresh = None;
+                        }
+                        KeyCode::Char('o') => {
+                            data.config.cgroups = !data.config.cgroups;
+                            data.last_slow_refresh = None;
+                        }
+                        _ => {}
+                    }
+                }
                 Event::Mouse(_) => {} // Ignorar eventos do mouse
                 _ => {}
             }
         }
     }
+}
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+/// `monitor fleet`: a visão geral dos agentes, com a interface completa de um host no Enter.
+fn run_fleet(
+    path: &Path,
+    config: AppConfig,
+    token: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut fleet = Fleet::load(path, token, config.history)?;
+    let mut terminal = enter_terminal()?;
+    let result = fleet_loop(&mut terminal, &mut fleet, &config);
+    leave_terminal(&mut terminal)?;
+    result
+}
+
+fn fleet_loop(
+    terminal: &mut Term,
+    fleet: &mut Fleet,
+    config: &AppConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    loop {
+        fleet.poll();
+        terminal.draw(|f| {
+            let size = f.size();
+            fleet.draw(f, size);
+        })?;
+
+        if !event::poll(Duration::from_millis(50))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(event::KeyModifiers::CONTROL) {
+            return Ok(());
+        }
+        match key.code {
+            KeyCode::Char('q') => return Ok(()),
+            KeyCode::Enter => {
+                let Some(host) = fleet.selected_mut() else {
+                    continue;
+                };
+                if open_host(terminal, &mut host.client, config)? == Exit::Interrupt {
+                    return Ok(());
+                }
+            }
+            _ => {
+                fleet.handle_key(key);
+            }
+        }
+    }
+}
+
+/// Abre a interface completa de um host da frota; `q` volta para a visão geral.
+fn open_host(
+    terminal: &mut Term,
+    client: &mut RemoteClient,
+    config: &AppConfig,
+) -> Result<Exit, Box<dyn std::error::Error>> {
+    // Sem nenhuma amostra ainda não há o que mostrar
+    let (Some(latest), Some(system_info)) = (client.recent().back(), client.system()) else {
+        return Ok(Exit::Quit);
+    };
+    let mut config = config.clone();
+    replay::configure(latest, &mut config);
+    let mut data = SystemData::new(config, system_info.clone())?;
+    // Os gráficos começam com o histórico que a frota já recebeu
+    for sample in client.recent() {
+        data.apply(sample);
+    }
+    client.take_system();
+
+    let sources = Sources {
+        sys: System::new(),
+        gpus: Vec::new(),
+        replay: None,
+        remote: Some(client),
+        recorder: None,
+        metrics: None,
+    };
+    let exit = run_tui(terminal, &mut data, sources)?;
+    terminal.clear()?;
+    Ok(exit)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    // Lidar com reset de configuração (antes de ler, para consertar um arquivo inválido)
+    if args.reset_config {
+        AppConfig::default().save()?;
+        println!("Configuration reset to defaults.");
+        return Ok(());
+    }
+    let mut config = AppConfig::load()?;
+
+    // Atualizar configuração com argumentos da linha de comando
+    config.update_from_args(&args);
+
+    // Salvar configuração se solicitado
+    if args.save_config {
+        config.save()?;
+        println!("Configuration saved as default.");
+        return Ok(());
+    }
+
+    if let Some(Command::Fleet { hosts }) = &args.command {
+        // A frota só mostra os agentes: não grava, não exporta e não coleta localmente
+        if args.record.is_some() || args.serve_metrics.is_some() {
+            return Err("--record and --serve-metrics cannot be used with `monitor fleet`".into());
+        }
+        if args.connect.is_some() || args.replay.is_some() || args.format != OutputFormat::Tui {
+            return Err(
+                "--connect, --replay and --format cannot be used with `monitor fleet`".into(),
+            );
+        }
+        return run_fleet(hosts, config, args.token.as_deref());
+    }
+
+    // Com --connect as amostras vêm de um agente; a primeira confirma endereço e token
+    let mut remote = match &args.connect {
+        Some(address) => {
+            let mut client = RemoteClient::start(address.clone(), args.token.clone(), 0);
+            let first = client
+                .wait_for_sample(CONNECT_WAIT)
+                .map_err(|e| format!("Cannot connect to {}: {}", address, e))?;
+            replay::configure(&first, &mut config);
+            Some((client, first))
+        }
+        None => None,
+    };
+
+    // O replay substitui a coleta: hardware e gráficos vêm da sessão gravada
+    let (system_info, replay) = match (&args.replay, remote.as_mut()) {
+        (Some(path), _) => {
+            let (system_info, samples) = replay::load(path)?;
+            let replay = Replay::new(samples);
+            replay.configure(&mut config);
+            (system_info, Some(replay))
+        }
+        (None, Some((client, _))) => (
+            client
+                .take_system()
+                .ok_or("The agent did not describe its hardware")?,
+            None,
+        ),
+        (None, None) => (SystemInfo::new()?, None),
+    };
+
+    // Backends de GPU; sem nenhum dispositivo o gráfico de GPU some
+    let collecting = replay.is_none() && remote.is_none();
+    let gpus = if config.no_gpu || !collecting {
+        Vec::new()
+    } else {
+        gpu::detect(args.gpu_backend, &config.sysfs_root)
+    };
+    if collecting && gpus.is_empty() {
+        config.no_gpu = true;
+    }
 
-    Ok(())
+    let recorder = match &args.record {
+        Some(path) => Some(Recorder::new(
+            path.clone(),
+            args.record_max_size.map(|mib| mib * 1024 * 1024),
+            args.record_daily,
+            &system_info,
+        )?),
+        None => None,
+    };
+
+    let metrics = match args.serve_metrics {
+        Some(address) => {
+            let metrics = MetricsServer::start(address)
+                .map_err(|e| format!("Cannot serve metrics on {}: {}", address, e))?;
+            eprintln!(
+                "monitor serving metrics on http://{}/metrics",
+                metrics.local_addr()
+            );
+            Some(metrics)
+        }
+        None => None,
+    };
+
+    if let Some(Command::Agent { listen, interval }) = &args.command {
+        // O padrão da TUI (50 ms) encheria o histórico dos clientes em poucos segundos
+        config.interval = match interval {
+            Some(interval) => *interval,
+            None if config.interval == AppConfig::default().interval => AGENT_INTERVAL_MS,
+            None => config.interval,
+        };
+        let agent = AgentServer::start(*listen, args.token.clone(), system_info.clone())
+            .map_err(|e| format!("Cannot listen on {}: {}", listen, e))?;
+        eprintln!("monitor agent listening on {}", agent.local_addr());
+        return run_headless(
+            config,
+            system_info,
+            gpus,
+            OutputFormat::None,
+            recorder,
+            metrics,
+            Some(agent),
+        );
+    }
+
+    if args.format != OutputFormat::Tui {
+        return run_headless(
+            config,
+            system_info,
+            gpus,
+            args.format,
+            recorder,
+            metrics,
+            None,
+        );
+    }
+
+    // Create app and system info (antes do terminal, para que erros de configuração apareçam)
+    let mut data = SystemData::new(config, system_info)?;
+    let mut remote = remote.map(|(client, first)| {
+        data.apply(&first);
+        client
+    });
+    let sources = Sources {
+        sys: System::new_all(),
+        gpus,
+        replay,
+        remote: remote.as_mut(),
+        recorder,
+        metrics,
+    };
+
+    let mut terminal = enter_terminal()?;
+    let result = run_tui(&mut terminal, &mut data, sources);
+    leave_terminal(&mut terminal)?;
+    result.map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::MockBackend;
+
+    fn system_info() -> SystemInfo {
+        SystemInfo {
+            cpu_model: "Test CPU".to_string(),
+            cpu_cores: 1,
+            cpu_threads: 1,
+            gpu_model: "Mock GPU".to_string(),
+            os_name: "Linux".to_string(),
+            os_version: "test".to_string(),
+        }
+    }
+
+    fn gpu_graph(data: &SystemData) -> &Graph {
+        data.graphs
+            .iter()
+            .find(|graph| graph.graph_type == ChartKind::Gpu)
+            .unwrap()
+    }
+
+    #[test]
+    fn every_gpu_gets_its_own_series() {
+        let mut data = SystemData::new(AppConfig::default(), system_info()).unwrap();
+        let mut backend = MockBackend::new(3);
+        data.gpus = backend.sample();
+        data.update_graphs();
+
+        let names: Vec<&str> = gpu_graph(&data)
+            .series
+            .iter()
+            .map(|series| series.name.as_str())
+            .collect();
+        assert_eq!(names.len(), 9);
+        assert_eq!(&names[3..6], ["gpu1 usage", "gpu1 mem", "gpu1 temp"]);
+        assert_eq!(data.snapshot().gpus.len(), 3);
+
+        // Visão geral: o uso de cada placa; selecionada: uso, memória e temperatura
+        assert_eq!(gpu_graph(&data).visible_series().count(), 3);
+        data.cycle_gpu();
+        data.cycle_gpu();
+        assert_eq!(gpu_graph(&data).selected.as_deref(), Some("gpu1"));
+        assert_eq!(gpu_graph(&data).visible_series().count(), 3);
+        assert!(gpu_graph(&data)
+            .visible_series()
+            .all(|series| series.name.starts_with("gpu1 ")));
+        data.cycle_gpu();
+        data.cycle_gpu();
+        assert_eq!(gpu_graph(&data).selected, None);
+    }
+
+    #[test]
+    fn gpu_alerts_use_the_hottest_device() {
+        let mut data = SystemData::new(AppConfig::default(), system_info()).unwrap();
+        data.gpus = MockBackend::new(4).sample();
+        let hottest = data
+            .gpus
+            .iter()
+            .filter_map(|gpu| gpu.temperature)
+            .fold(0.0, f64::max);
+        assert_eq!(data.metric_value(&Metric::GpuTemp), Some(hottest));
+    }
 }
@@ -0,0 +1,690 @@
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::gpu::GpuStats;
+use crate::snapshot::Snapshot;
+
+/// Exporter Prometheus: serve a última amostra em `/metrics` via HTTP simples.
+pub struct MetricsServer {
+    latest: Arc<Mutex<Option<String>>>,
+    address: SocketAddr,
+}
+
+impl MetricsServer {
+    pub fn start(address: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let latest = Arc::new(Mutex::new(None));
+
+        let shared = Arc::clone(&latest);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let latest = Arc::clone(&shared);
+                // Uma thread por conexão, para que um cliente parado não trave os scrapes;
+                // erros de um cliente não derrubam o exporter
+                thread::spawn(move || {
+                    let _ = handle(stream, &latest);
+                });
+            }
+        });
+
+        Ok(Self { latest, address })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn publish(&self, snapshot: &Snapshot) {
+        let body = render(snapshot);
+        if let Ok(mut latest) = self.latest.lock() {
+            *latest = Some(body);
+        }
+    }
+}
+
+fn handle(stream: TcpStream, latest: &Mutex<Option<String>>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Descartar os cabeçalhos da requisição
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => match latest.lock().ok().and_then(|l| l.clone()) {
+            Some(body) => ("200 OK", "text/plain; version=0.0.4", body),
+            None => (
+                "503 Service Unavailable",
+                "text/plain",
+                "no samples yet\n".to_string(),
+            ),
+        },
+        ("GET", "/") => (
+            "200 OK",
+            "text/html",
+            "<a href=\"/metrics\">/metrics</a>\n".to_string(),
+        ),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Valores não finitos têm grafia própria no formato de texto (`inf` do Rust é rejeitado).
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+struct Exposition {
+    text: String,
+}
+
+impl Exposition {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.text, "# HELP {} {}", name, help);
+        let _ = writeln!(self.text, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        let value = format_value(value);
+        if labels.is_empty() {
+            let _ = writeln!(self.text, "{} {}", name, value);
+        } else {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, escape(value)))
+                .collect();
+            let _ = writeln!(self.text, "{}{{{}}} {}", name, labels.join(","), value);
+        }
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, "gauge", help);
+        self.sample(name, &[], value);
+    }
+}
+
+type GpuValue = fn(&GpuStats) -> Option<f64>;
+
+/// Converte uma amostra para o formato de exposição em texto do Prometheus.
+pub fn render(snapshot: &Snapshot) -> String {
+    let mut out = Exposition {
+        text: String::new(),
+    };
+
+    if let Some(system) = &snapshot.system {
+        out.family(
+            "monitor_load_average",
+            "gauge",
+            "Load average over 1, 5 and 15 minutes.",
+        );
+        for (period, load) in ["1m", "5m", "15m"].iter().zip(system.load) {
+            out.sample("monitor_load_average", &[("period", period)], load);
+        }
+        out.gauge(
+            "monitor_uptime_seconds",
+            "Time since boot.",
+            system.uptime as f64,
+        );
+        out.gauge(
+            "monitor_boot_time_seconds",
+            "Boot time as a Unix timestamp.",
+            system.boot_time as f64,
+        );
+        if let Some(activity) = &system.activity {
+            out.gauge(
+                "monitor_context_switches_per_second",
+                "Context switches per second.",
+                activity.context_switches,
+            );
+            out.gauge(
+                "monitor_interrupts_per_second",
+                "Interrupts per second.",
+                activity.interrupts,
+            );
+            out.gauge(
+                "monitor_forks_per_second",
+                "Processes created per second.",
+                activity.forks,
+            );
+            out.family("monitor_tasks", "gauge", "Tasks by state.");
+            for (state, count) in [
+                ("running", activity.running),
+                ("blocked", activity.blocked),
+                ("total", activity.total),
+            ] {
+                out.sample("monitor_tasks", &[("state", state)], count as f64);
+            }
+        }
+    }
+
+    out.gauge(
+        "monitor_cpu_usage_percent",
+        "Global CPU usage.",
+        snapshot.cpu.usage,
+    );
+    out.family(
+        "monitor_cpu_core_usage_percent",
+        "gauge",
+        "CPU usage per logical core.",
+    );
+    for (i, usage) in snapshot.cpu.cores.iter().enumerate() {
+        out.sample(
+            "monitor_cpu_core_usage_percent",
+            &[("core", &i.to_string())],
+            *usage,
+        );
+    }
+
+    out.gauge(
+        "monitor_memory_total_bytes",
+        "Total physical memory.",
+        snapshot.memory.total as f64,
+    );
+    out.gauge(
+        "monitor_memory_used_bytes",
+        "Used physical memory.",
+        snapshot.memory.used as f64,
+    );
+    out.gauge(
+        "monitor_memory_available_bytes",
+        "Memory available for new allocations.",
+        snapshot.memory.available as f64,
+    );
+    out.gauge(
+        "monitor_swap_total_bytes",
+        "Total swap space.",
+        snapshot.swap.total as f64,
+    );
+    out.gauge(
+        "monitor_swap_used_bytes",
+        "Used swap space.",
+        snapshot.swap.used as f64,
+    );
+
+    if let Some(times) = &snapshot.cpu.times {
+        out.family(
+            "monitor_cpu_time_percent",
+            "gauge",
+            "Share of CPU time per state.",
+        );
+        for (mode, share) in times.stacked() {
+            out.sample("monitor_cpu_time_percent", &[("mode", mode)], share);
+        }
+        out.sample("monitor_cpu_time_percent", &[("mode", "idle")], times.idle);
+    }
+    if !snapshot.cpu.frequencies_mhz.is_empty() {
+        out.family(
+            "monitor_cpu_core_frequency_hertz",
+            "gauge",
+            "Current clock per logical core.",
+        );
+        for (i, mhz) in snapshot.cpu.frequencies_mhz.iter().enumerate() {
+            out.sample(
+                "monitor_cpu_core_frequency_hertz",
+                &[("core", &i.to_string())],
+                mhz * 1e6,
+            );
+        }
+        out.gauge(
+            "monitor_cpu_throttled",
+            "1 while the CPU is throttling (thermal events or low clocks under load).",
+            if snapshot.cpu.throttling.is_some() {
+                1.0
+            } else {
+                0.0
+            },
+        );
+    }
+    if let Some(power) = &snapshot.cpu.power {
+        out.family(
+            "monitor_cpu_power_watts",
+            "gauge",
+            "CPU power draw per RAPL domain.",
+        );
+        out.sample(
+            "monitor_cpu_power_watts",
+            &[("domain", "package")],
+            power.package,
+        );
+        for (domain, watts) in [("core", power.core), ("dram", power.dram)] {
+            if let Some(watts) = watts {
+                out.sample("monitor_cpu_power_watts", &[("domain", domain)], watts);
+            }
+        }
+    }
+    if let Some(energy) = &snapshot.energy {
+        out.family(
+            "monitor_energy_joules_total",
+            "counter",
+            "Energy used since monitor started.",
+        );
+        out.sample(
+            "monitor_energy_joules_total",
+            &[("source", "cpu")],
+            energy.cpu_joules,
+        );
+        out.sample(
+            "monitor_energy_joules_total",
+            &[("source", "gpu")],
+            energy.gpu_joules,
+        );
+    }
+
+    if let Some(pressure) = &snapshot.pressure {
+        let resources: Vec<_> = pressure
+            .resources()
+            .into_iter()
+            .filter_map(|(name, pressure)| Some((name, pressure?)))
+            .flat_map(|(name, pressure)| {
+                let full = pressure.full.map(|full| (name, "full", full));
+                [Some((name, "some", pressure.some)), full]
+            })
+            .flatten()
+            .collect();
+        out.family(
+            "monitor_pressure_stall_percent",
+            "gauge",
+            "Share of time tasks were stalled on a resource (PSI).",
+        );
+        for (resource, kind, stall) in &resources {
+            for (window, value) in [
+                ("10s", stall.avg10),
+                ("60s", stall.avg60),
+                ("300s", stall.avg300),
+            ] {
+                out.sample(
+                    "monitor_pressure_stall_percent",
+                    &[("resource", resource), ("kind", kind), ("window", window)],
+                    value,
+                );
+            }
+        }
+        out.family(
+            "monitor_pressure_stall_seconds_total",
+            "counter",
+            "Total time tasks were stalled on a resource (PSI).",
+        );
+        for (resource, kind, stall) in &resources {
+            out.sample(
+                "monitor_pressure_stall_seconds_total",
+                &[("resource", resource), ("kind", kind)],
+                stall.total as f64 / 1e6,
+            );
+        }
+    }
+
+    if let Some(cgroup) = &snapshot.cgroup {
+        let labels = [("cgroup", cgroup.path.as_str())];
+        if let Some(max) = cgroup.memory_max {
+            out.family(
+                "monitor_cgroup_memory_max_bytes",
+                "gauge",
+                "Memory limit of the cgroup (memory.max).",
+            );
+            out.sample("monitor_cgroup_memory_max_bytes", &labels, max as f64);
+        }
+        if let Some(quota) = cgroup.cpu_quota {
+            out.family(
+                "monitor_cgroup_cpu_quota_cores",
+                "gauge",
+                "CPU quota of the cgroup in cores (cpu.max).",
+            );
+            out.sample("monitor_cgroup_cpu_quota_cores", &labels, quota);
+        }
+        out.family(
+            "monitor_cgroup_cpu_throttled_percent",
+            "gauge",
+            "Share of recent CPU periods in which the cgroup was throttled.",
+        );
+        out.sample(
+            "monitor_cgroup_cpu_throttled_percent",
+            &labels,
+            cgroup.throttled_percent,
+        );
+        out.family(
+            "monitor_cgroup_cpu_throttled_periods_total",
+            "counter",
+            "CPU periods in which the cgroup was throttled.",
+        );
+        out.sample(
+            "monitor_cgroup_cpu_throttled_periods_total",
+            &labels,
+            cgroup.throttled_periods as f64,
+        );
+        out.family(
+            "monitor_cgroup_cpu_throttled_seconds_total",
+            "counter",
+            "Time the cgroup spent throttled.",
+        );
+        out.sample(
+            "monitor_cgroup_cpu_throttled_seconds_total",
+            &labels,
+            cgroup.throttled_seconds,
+        );
+    }
+
+    if let Some(network) = &snapshot.network {
+        out.family(
+            "monitor_network_receive_bytes_total",
+            "counter",
+            "Bytes received per interface.",
+        );
+        for interface in &network.interfaces {
+            out.sample(
+                "monitor_network_receive_bytes_total",
+                &[("interface", &interface.name)],
+                interface.rx_total as f64,
+            );
+        }
+        out.family(
+            "monitor_network_transmit_bytes_total",
+            "counter",
+            "Bytes transmitted per interface.",
+        );
+        for interface in &network.interfaces {
+            out.sample(
+                "monitor_network_transmit_bytes_total",
+                &[("interface", &interface.name)],
+                interface.tx_total as f64,
+            );
+        }
+        out.family(
+            "monitor_network_receive_bytes_per_second",
+            "gauge",
+            "Current receive rate per interface.",
+        );
+        for interface in &network.interfaces {
+            out.sample(
+                "monitor_network_receive_bytes_per_second",
+                &[("interface", &interface.name)],
+                interface.rx_rate,
+            );
+        }
+        out.family(
+            "monitor_network_transmit_bytes_per_second",
+            "gauge",
+            "Current transmit rate per interface.",
+        );
+        for interface in &network.interfaces {
+            out.sample(
+                "monitor_network_transmit_bytes_per_second",
+                &[("interface", &interface.name)],
+                interface.tx_rate,
+            );
+        }
+    }
+
+    // Uma série por GPU, identificada pelo índice e pelo modelo
+    let gpu_families: [(&str, &str, GpuValue); 5] = [
+        ("monitor_gpu_utilization_percent", "GPU utilization.", |g| {
+            Some(g.usage)
+        }),
+        (
+            "monitor_gpu_memory_used_percent",
+            "GPU memory in use.",
+            |g| Some(g.memory_percent),
+        ),
+        ("monitor_gpu_memory_used_bytes", "GPU memory in use.", |g| {
+            Some(g.memory_used as f64)
+        }),
+        ("monitor_gpu_temperature_celsius", "GPU temperature.", |g| {
+            g.temperature
+        }),
+        ("monitor_gpu_power_watts", "GPU power draw.", |g| {
+            g.power_watts
+        }),
+    ];
+    if !snapshot.gpus.is_empty() {
+        for (name, help, value) in gpu_families {
+            out.family(name, "gauge", help);
+            for (i, device) in snapshot.gpus.iter().enumerate() {
+                if let Some(value) = value(device) {
+                    let index = i.to_string();
+                    out.sample(name, &[("gpu", &index), ("model", &device.model)], value);
+                }
+            }
+        }
+    }
+
+    out.text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cgroup::CgroupStats;
+    use crate::cpufreq::ThrottleReason;
+    use crate::network::InterfaceStats;
+    use crate::pressure::{Pressure, PressureStats, Stall};
+    use crate::procstat::{CpuBreakdown, KernelActivity};
+    use crate::rapl::{CpuPower, SessionEnergy};
+    use crate::snapshot::{CpuSnapshot, MemorySnapshot, NetworkSnapshot, SystemSnapshot};
+    use std::io::Read;
+    use std::time::Instant;
+
+    fn snapshot() -> Snapshot {
+        Snapshot {
+            timestamp: "2024-01-01T00:00:00+00:00".to_string(),
+            timestamp_ms: 1_704_067_200_000,
+            system: Some(SystemSnapshot {
+                load: [0.5, 1.25, 2.0],
+                uptime: 3_600,
+                boot_time: 1_704_063_600,
+                activity: Some(KernelActivity {
+                    context_switches: 1_500.0,
+                    interrupts: 800.0,
+                    forks: 3.0,
+                    running: 2,
+                    total: 300,
+                    blocked: 1,
+                }),
+            }),
+            cpu: CpuSnapshot {
+                usage: 42.5,
+                cores: vec![10.0, 75.0],
+                power: Some(CpuPower {
+                    package: 35.5,
+                    core: Some(20.0),
+                    dram: None,
+                }),
+                frequencies_mhz: vec![3200.0, 800.0],
+                throttling: Some(ThrottleReason::Thermal),
+                times: Some(CpuBreakdown {
+                    user: 30.0,
+                    iowait: 12.5,
+                    idle: 57.5,
+                    ..Default::default()
+                }),
+                core_times: Vec::new(),
+            },
+            memory: MemorySnapshot {
+                total: 8_000,
+                used: 2_000,
+                available: 6_000,
+                percent: 25.0,
+            },
+            swap: MemorySnapshot {
+                total: 1_000,
+                used: 0,
+                available: 1_000,
+                percent: 0.0,
+            },
+            pressure: Some(PressureStats {
+                memory: Some(Pressure {
+                    some: Stall {
+                        avg10: 12.5,
+                        avg60: 4.0,
+                        avg300: 1.0,
+                        total: 2_500_000,
+                    },
+                    full: None,
+                }),
+                ..Default::default()
+            }),
+            cgroup: Some(CgroupStats {
+                path: "/ci.slice/job-42.scope".to_string(),
+                memory_max: Some(8_000),
+                memory_used: Some(2_000),
+                cpu_quota: Some(2.0),
+                cpu_percent: Some(42.5),
+                throttled_percent: 40.0,
+                throttled_periods: 30,
+                throttled_seconds: 0.9,
+                ..Default::default()
+            }),
+            gpus: vec![GpuStats {
+                model: "Test GPU".to_string(),
+                usage: 90.0,
+                memory_percent: 50.0,
+                memory_used: 4_000,
+                memory_total: 8_000,
+                temperature: Some(70.0),
+                power_watts: Some(250.0),
+                ..Default::default()
+            }],
+            energy: Some(SessionEnergy {
+                cpu_joules: 1_000.0,
+                gpu_joules: 5_000.0,
+            }),
+            network: Some(NetworkSnapshot {
+                rx_rate: 100.0,
+                tx_rate: 50.0,
+                rx_total: 1_000,
+                tx_total: 500,
+                interfaces: vec![InterfaceStats {
+                    name: "eth0".to_string(),
+                    rx_rate: 100.0,
+                    tx_rate: 50.0,
+                    rx_total: 1_000,
+                    tx_total: 500,
+                }],
+            }),
+        }
+    }
+
+    fn scrape(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn renders_cpu_memory_network_and_gpu() {
+        let text = render(&snapshot());
+        assert!(text.contains("monitor_cpu_usage_percent 42.5\n"));
+        assert!(text.contains("monitor_load_average{period=\"5m\"} 1.25\n"));
+        assert!(text.contains("monitor_tasks{state=\"blocked\"} 1\n"));
+        assert!(text.contains(
+            "monitor_pressure_stall_percent{resource=\"memory\",kind=\"some\",window=\"10s\"} 12.5\n"
+        ));
+        assert!(text.contains(
+            "monitor_pressure_stall_seconds_total{resource=\"memory\",kind=\"some\"} 2.5\n"
+        ));
+        assert!(!text.contains("resource=\"cpu\""));
+        assert!(text.contains(
+            "monitor_cgroup_cpu_throttled_periods_total{cgroup=\"/ci.slice/job-42.scope\"} 30\n"
+        ));
+        assert!(
+            text.contains("monitor_cgroup_cpu_quota_cores{cgroup=\"/ci.slice/job-42.scope\"} 2\n")
+        );
+        assert!(text.contains("monitor_cpu_core_usage_percent{core=\"1\"} 75\n"));
+        assert!(text.contains("monitor_cpu_core_frequency_hertz{core=\"0\"} 3200000000\n"));
+        assert!(text.contains("monitor_cpu_throttled 1\n"));
+        assert!(text.contains("monitor_cpu_time_percent{mode=\"iowait\"} 12.5\n"));
+        assert!(text.contains("monitor_memory_used_bytes 2000\n"));
+        assert!(text.contains("monitor_network_receive_bytes_total{interface=\"eth0\"} 1000\n"));
+        assert!(text.contains("# TYPE monitor_network_transmit_bytes_total counter\n"));
+        assert!(text.contains("monitor_gpu_temperature_celsius{gpu=\"0\",model=\"Test GPU\"} 70\n"));
+        assert!(text.contains("monitor_gpu_power_watts{gpu=\"0\",model=\"Test GPU\"} 250\n"));
+        assert!(text.contains("monitor_cpu_power_watts{domain=\"core\"} 20\n"));
+        assert!(!text.contains("domain=\"dram\""));
+        assert!(text.contains("monitor_energy_joules_total{source=\"gpu\"} 5000\n"));
+    }
+
+    #[test]
+    fn spells_non_finite_values_for_prometheus() {
+        let mut snapshot = snapshot();
+        snapshot.cpu.usage = f64::NAN;
+        let system = snapshot.system.as_mut().unwrap();
+        system.load = [f64::INFINITY, f64::NEG_INFINITY, 1.0];
+        let text = render(&snapshot);
+        assert!(text.contains("monitor_cpu_usage_percent NaN\n"));
+        assert!(text.contains("monitor_load_average{period=\"1m\"} +Inf\n"));
+        assert!(text.contains("monitor_load_average{period=\"5m\"} -Inf\n"));
+        assert!(!text.contains("inf\n"));
+    }
+
+    #[test]
+    fn omits_gpu_without_devices() {
+        let mut snapshot = snapshot();
+        snapshot.gpus.clear();
+        let text = render(&snapshot);
+        assert!(!text.contains("monitor_gpu_"));
+    }
+
+    #[test]
+    fn serves_metrics_over_http() {
+        let server = MetricsServer::start("127.0.0.1:0".parse().unwrap()).unwrap();
+
+        let response = scrape(server.local_addr(), "/metrics");
+        assert!(response.starts_with("HTTP/1.1 503"));
+
+        server.publish(&snapshot());
+        let response = scrape(server.local_addr(), "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("monitor_cpu_usage_percent 42.5"));
+
+        let response = scrape(server.local_addr(), "/nope");
+        assert!(response.starts_with("HTTP/1.1 404"));
+    }
+
+    #[test]
+    fn stalled_clients_do_not_block_scrapes() {
+        let server = MetricsServer::start("127.0.0.1:0".parse().unwrap()).unwrap();
+        server.publish(&snapshot());
+
+        // Conexão aberta que nunca envia a requisição
+        let _stalled = TcpStream::connect(server.local_addr()).unwrap();
+        let start = Instant::now();
+        let response = scrape(server.local_addr(), "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
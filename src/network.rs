@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+use sysinfo::{NetworkExt, System, SystemExt};
+
+/// Tráfego de uma interface de rede; taxas em bytes por segundo.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InterfaceStats {
+    pub name: String,
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+    pub rx_total: u64,
+    pub tx_total: u64,
+}
+
+/// Guarda os contadores acumulados de cada interface para calcular taxas.
+///
+/// `NetworkExt::received()` é só o delta desde o último refresh, então a taxa
+/// é calculada a partir dos totais e do tempo real decorrido entre amostras.
+#[derive(Default)]
+pub struct NetworkTracker {
+    previous: HashMap<String, (u64, u64)>,
+    last: Option<Instant>,
+}
+
+impl NetworkTracker {
+    pub fn update(&mut self, sys: &System) -> Vec<InterfaceStats> {
+        let counters = sys
+            .networks()
+            .into_iter()
+            .map(|(name, data)| {
+                (
+                    name.clone(),
+                    data.total_received(),
+                    data.total_transmitted(),
+                )
+            })
+            .collect();
+        self.update_from(counters, Instant::now())
+    }
+
+    fn update_from(
+        &mut self,
+        counters: Vec<(String, u64, u64)>,
+        now: Instant,
+    ) -> Vec<InterfaceStats> {
+        let elapsed = self
+            .last
+            .map(|last| now.duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+        self.last = Some(now);
+
+        let mut interfaces = Vec::new();
+        let mut current = HashMap::new();
+        for (name, rx_total, tx_total) in counters {
+            // Contador zerado (interface recriada) conta como amostra inicial
+            let (rx_rate, tx_rate) = match self.previous.get(&name) {
+                Some(&(prev_rx, prev_tx))
+                    if elapsed > 0.0 && rx_total >= prev_rx && tx_total >= prev_tx =>
+                {
+                    (
+                        (rx_total - prev_rx) as f64 / elapsed,
+                        (tx_total - prev_tx) as f64 / elapsed,
+                    )
+                }
+                _ => (0.0, 0.0),
+            };
+            interfaces.push(InterfaceStats {
+                name: name.clone(),
+                rx_rate,
+                tx_rate,
+                rx_total,
+                tx_total,
+            });
+            current.insert(name, (rx_total, tx_total));
+        }
+        self.previous = current;
+
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        interfaces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn sample(name: &str, rx: u64, tx: u64) -> Vec<(String, u64, u64)> {
+        vec![(name.to_string(), rx, tx)]
+    }
+
+    #[test]
+    fn first_sample_has_no_rate() {
+        let mut tracker = NetworkTracker::default();
+        let stats = tracker.update_from(sample("eth0", 1_000, 500), Instant::now());
+        assert_eq!(stats[0].rx_rate, 0.0);
+        assert_eq!(stats[0].tx_rate, 0.0);
+        assert_eq!(stats[0].rx_total, 1_000);
+        assert_eq!(stats[0].tx_total, 500);
+    }
+
+    #[test]
+    fn rate_is_per_second_regardless_of_interval() {
+        let start = Instant::now();
+
+        // 50 ms entre amostras
+        let mut fast = NetworkTracker::default();
+        fast.update_from(sample("eth0", 0, 0), start);
+        let stats = fast.update_from(sample("eth0", 500, 50), start + Duration::from_millis(50));
+        assert!((stats[0].rx_rate - 10_000.0).abs() < 1e-6);
+        assert!((stats[0].tx_rate - 1_000.0).abs() < 1e-6);
+
+        // 2 s entre amostras com o mesmo tráfego por segundo
+        let mut slow = NetworkTracker::default();
+        slow.update_from(sample("eth0", 0, 0), start);
+        let stats = slow.update_from(
+            sample("eth0", 20_000, 2_000),
+            start + Duration::from_secs(2),
+        );
+        assert!((stats[0].rx_rate - 10_000.0).abs() < 1e-6);
+        assert!((stats[0].tx_rate - 1_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn uses_actual_elapsed_time_between_samples() {
+        let start = Instant::now();
+        let mut tracker = NetworkTracker::default();
+        tracker.update_from(sample("eth0", 0, 0), start);
+        tracker.update_from(sample("eth0", 1_000, 0), start + Duration::from_millis(100));
+        // Amostra atrasada: 300 ms depois da anterior
+        let stats =
+            tracker.update_from(sample("eth0", 4_000, 0), start + Duration::from_millis(400));
+        assert!((stats[0].rx_rate - 10_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn counter_reset_is_treated_as_a_new_baseline() {
+        let start = Instant::now();
+        let mut tracker = NetworkTracker::default();
+        tracker.update_from(sample("eth0", 10_000, 10_000), start);
+        let stats = tracker.update_from(sample("eth0", 100, 100), start + Duration::from_secs(1));
+        assert_eq!(stats[0].rx_rate, 0.0);
+        assert_eq!(stats[0].tx_rate, 0.0);
+
+        let stats = tracker.update_from(sample("eth0", 1_100, 600), start + Duration::from_secs(2));
+        assert!((stats[0].rx_rate - 1_000.0).abs() < 1e-6);
+        assert!((stats[0].tx_rate - 500.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn interfaces_are_tracked_independently() {
+        let start = Instant::now();
+        let mut tracker = NetworkTracker::default();
+        tracker.update_from(sample("eth0", 0, 0), start);
+        let stats = tracker.update_from(
+            vec![
+                ("wlan0".to_string(), 5_000, 5_000),
+                ("eth0".to_string(), 2_000, 1_000),
+            ],
+            start + Duration::from_secs(1),
+        );
+        assert_eq!(stats[0].name, "eth0");
+        assert!((stats[0].rx_rate - 2_000.0).abs() < 1e-6);
+        // Interface nova: ainda sem taxa
+        assert_eq!(stats[1].name, "wlan0");
+        assert_eq!(stats[1].rx_rate, 0.0);
+    }
+}
@@ -0,0 +1,209 @@
+use std::path::Path;
+use std::time::Duration;
+
+use crate::sysfs::{read_number, read_string};
+
+/// Uma bateria de `/sys/class/power_supply`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Battery {
+    pub name: String,
+    /// Carga em %
+    pub percent: f64,
+    /// "Charging", "Discharging", "Full", "Not charging"...
+    pub state: String,
+    /// Potência entrando ou saindo da bateria, em W
+    pub watts: Option<f64>,
+    /// Tempo até descarregar (descarregando) ou até completar (carregando)
+    pub time_left: Option<Duration>,
+    /// Capacidade total atual em relação à de fábrica, em %
+    pub health: Option<f64>,
+}
+
+impl Battery {
+    pub fn is_discharging(&self) -> bool {
+        self.state == "Discharging"
+    }
+
+    pub fn is_charging(&self) -> bool {
+        self.state == "Charging"
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PowerStatus {
+    pub batteries: Vec<Battery>,
+    /// Fonte ligada na tomada; `None` se não houver fonte "Mains"
+    pub ac_online: Option<bool>,
+}
+
+/// Lê baterias e fontes em `<root>/class/power_supply`.
+pub fn read_power_supplies(sysfs_root: &Path) -> PowerStatus {
+    let mut status = PowerStatus::default();
+    let Ok(entries) = std::fs::read_dir(sysfs_root.join("class/power_supply")) else {
+        return status;
+    };
+    let mut supplies: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+    supplies.sort();
+
+    for supply in supplies {
+        match read_string(&supply.join("type")).as_deref() {
+            Some("Battery") => {
+                // Baterias de periféricos (mouse, fone) têm scope "Device"
+                if read_string(&supply.join("scope")).as_deref() == Some("Device") {
+                    continue;
+                }
+                status.batteries.push(read_battery(&supply));
+            }
+            Some("Mains") => {
+                let online = read_number(&supply.join("online")) == Some(1);
+                status.ac_online = Some(status.ac_online.unwrap_or(false) || online);
+            }
+            _ => {}
+        }
+    }
+    status
+}
+
+/// O driver expõe energia (µWh, µW) ou carga (µAh, µA); as contas são as mesmas nas duas.
+fn read_battery(path: &Path) -> Battery {
+    // Alguns drivers informam a corrente com sinal (negativa ao descarregar)
+    let value = |name: &str| {
+        read_string(&path.join(name))?
+            .parse::<f64>()
+            .ok()
+            .map(f64::abs)
+    };
+    let state = read_string(&path.join("status")).unwrap_or_else(|| "Unknown".to_string());
+
+    let (now, full, design, rate) = match value("energy_now") {
+        Some(now) => (
+            Some(now),
+            value("energy_full"),
+            value("energy_full_design"),
+            value("power_now"),
+        ),
+        None => (
+            value("charge_now"),
+            value("charge_full"),
+            value("charge_full_design"),
+            value("current_now"),
+        ),
+    };
+
+    // power_now em µW; sem ele, corrente (µA) x tensão (µV)
+    let watts = value("power_now")
+        .or_else(|| Some(value("current_now")? * value("voltage_now")? / 1e6))
+        .map(|microwatts| microwatts / 1e6);
+
+    let percent = value("capacity")
+        .or_else(|| Some(now? / full? * 100.0))
+        .unwrap_or(0.0);
+
+    let hours = match (now, full, rate) {
+        (Some(now), _, Some(rate)) if rate > 0.0 && state == "Discharging" => Some(now / rate),
+        (Some(now), Some(full), Some(rate)) if rate > 0.0 && state == "Charging" => {
+            Some((full - now).max(0.0) / rate)
+        }
+        _ => None,
+    };
+
+    Battery {
+        name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        percent,
+        state,
+        watts,
+        time_left: hours.map(|h| Duration::from_secs_f64(h * 3600.0)),
+        health: match (full, design) {
+            (Some(full), Some(design)) if design > 0.0 => Some(full / design * 100.0),
+            _ => None,
+        },
+    }
+}
+
+/// `3h 05m`
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysfs;
+
+    #[test]
+    fn reads_energy_based_battery_and_ac() {
+        let root = sysfs::fixture(
+            "power-energy",
+            &[
+                ("class/power_supply/AC/type", "Mains\n"),
+                ("class/power_supply/AC/online", "0\n"),
+                ("class/power_supply/BAT0/type", "Battery\n"),
+                ("class/power_supply/BAT0/status", "Discharging\n"),
+                ("class/power_supply/BAT0/capacity", "75\n"),
+                ("class/power_supply/BAT0/energy_now", "30000000\n"),
+                ("class/power_supply/BAT0/energy_full", "40000000\n"),
+                ("class/power_supply/BAT0/energy_full_design", "50000000\n"),
+                ("class/power_supply/BAT0/power_now", "12000000\n"),
+                // Bateria de periférico não entra
+                ("class/power_supply/hidpp_battery_0/type", "Battery\n"),
+                ("class/power_supply/hidpp_battery_0/scope", "Device\n"),
+            ],
+        );
+
+        let status = read_power_supplies(&root);
+        assert_eq!(status.ac_online, Some(false));
+        assert_eq!(status.batteries.len(), 1);
+
+        let battery = &status.batteries[0];
+        assert_eq!(battery.name, "BAT0");
+        assert_eq!(battery.percent, 75.0);
+        assert!(battery.is_discharging());
+        assert_eq!(battery.watts, Some(12.0));
+        // 30 Wh a 12 W
+        assert_eq!(battery.time_left, Some(Duration::from_secs(9000)));
+        assert_eq!(battery.health, Some(80.0));
+        assert_eq!(format_duration(battery.time_left.unwrap()), "2h 30m");
+    }
+
+    #[test]
+    fn reads_charge_based_battery_while_charging() {
+        let root = sysfs::fixture(
+            "power-charge",
+            &[
+                ("class/power_supply/ADP1/type", "Mains\n"),
+                ("class/power_supply/ADP1/online", "1\n"),
+                ("class/power_supply/BAT1/type", "Battery\n"),
+                ("class/power_supply/BAT1/status", "Charging\n"),
+                ("class/power_supply/BAT1/charge_now", "2000000\n"),
+                ("class/power_supply/BAT1/charge_full", "4000000\n"),
+                ("class/power_supply/BAT1/charge_full_design", "4000000\n"),
+                ("class/power_supply/BAT1/current_now", "1000000\n"),
+                ("class/power_supply/BAT1/voltage_now", "12000000\n"),
+            ],
+        );
+
+        let status = read_power_supplies(&root);
+        assert_eq!(status.ac_online, Some(true));
+
+        let battery = &status.batteries[0];
+        // Sem `capacity`, a carga vem de charge_now / charge_full
+        assert_eq!(battery.percent, 50.0);
+        assert!(battery.is_charging());
+        assert_eq!(battery.watts, Some(12.0));
+        // Faltam 2 Ah a 1 A
+        assert_eq!(battery.time_left, Some(Duration::from_secs(7200)));
+        assert_eq!(battery.health, Some(100.0));
+    }
+
+    #[test]
+    fn missing_power_supply_directory_means_no_batteries() {
+        let root = sysfs::fixture("power-none", &[]);
+        let status = read_power_supplies(&root);
+        assert!(status.batteries.is_empty());
+        assert_eq!(status.ac_online, None);
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Uma linha de /proc/pressure: % do tempo em espera nas janelas de 10s, 60s e 300s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stall {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    /// Tempo total em espera desde o boot, em µs
+    pub total: u64,
+}
+
+impl Stall {
+    fn parse(fields: &str) -> Option<Stall> {
+        let mut stall = Stall::default();
+        for field in fields.split_whitespace() {
+            let (key, value) = field.split_once('=')?;
+            match key {
+                "avg10" => stall.avg10 = value.parse().ok()?,
+                "avg60" => stall.avg60 = value.parse().ok()?,
+                "avg300" => stall.avg300 = value.parse().ok()?,
+                "total" => stall.total = value.parse().ok()?,
+                _ => {}
+            }
+        }
+        Some(stall)
+    }
+}
+
+/// `some`: alguma tarefa esperando; `full`: todas esperando (não existe para CPU em kernels antigos).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pressure {
+    pub some: Stall,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full: Option<Stall>,
+}
+
+pub fn parse_pressure(contents: &str) -> Option<Pressure> {
+    let mut some = None;
+    let mut full = None;
+    for line in contents.lines() {
+        match line.split_once(' ') {
+            Some(("some", fields)) => some = Stall::parse(fields),
+            Some(("full", fields)) => full = Stall::parse(fields),
+            _ => {}
+        }
+    }
+    Some(Pressure { some: some?, full })
+}
+
+/// PSI de CPU, memória e I/O; vazio em kernels sem suporte (ou com `psi=0`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PressureStats {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<Pressure>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Pressure>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub io: Option<Pressure>,
+}
+
+impl PressureStats {
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_none() && self.memory.is_none() && self.io.is_none()
+    }
+
+    pub fn resources(&self) -> [(&'static str, Option<Pressure>); 3] {
+        [("cpu", self.cpu), ("memory", self.memory), ("io", self.io)]
+    }
+}
+
+/// Lê `<dir>/{cpu,memory,io}`; normalmente `dir` é `/proc/pressure`.
+pub fn read_pressure(dir: &Path) -> PressureStats {
+    let read = |name: &str| {
+        std::fs::read_to_string(dir.join(name))
+            .ok()
+            .and_then(|contents| parse_pressure(&contents))
+    };
+    PressureStats {
+        cpu: read("cpu"),
+        memory: read("memory"),
+        io: read("io"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_some_and_full_lines() {
+        let pressure = parse_pressure(
+            "some avg10=1.53 avg60=0.87 avg300=0.22 total=4205783\n\
+             full avg10=0.25 avg60=0.10 avg300=0.00 total=1283957\n",
+        )
+        .unwrap();
+        assert_eq!(pressure.some.avg10, 1.53);
+        assert_eq!(pressure.some.avg300, 0.22);
+        assert_eq!(pressure.some.total, 4205783);
+        assert_eq!(pressure.full.unwrap().avg10, 0.25);
+
+        // CPU em kernels antes do 5.13 só tem `some`
+        let cpu = parse_pressure("some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n").unwrap();
+        assert_eq!(cpu.full, None);
+        assert_eq!(parse_pressure(""), None);
+    }
+}
@@ -0,0 +1,666 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use humansize::{format_size, BINARY};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use sysinfo::{Pid, PidExt, ProcessExt, Signal, System, SystemExt, UserExt};
+use tui::{
+    layout::Constraint,
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+};
+
+#[derive(Clone)]
+pub struct ProcessInfo {
+    pub pid: Pid,
+    pub parent: Option<Pid>,
+    pub name: String,
+    pub user: String,
+    pub cpu: f32,
+    pub memory: u64,
+    pub state: String,
+    pub command: String,
+}
+
+impl ProcessInfo {
+    fn matches(&self, filter: &str) -> bool {
+        filter.is_empty()
+            || self.pid.to_string().contains(filter)
+            || self.name.to_lowercase().contains(filter)
+            || self.user.to_lowercase().contains(filter)
+            || self.command.to_lowercase().contains(filter)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SortColumn {
+    Pid,
+    Name,
+    User,
+    Cpu,
+    Memory,
+    State,
+    Command,
+}
+
+impl SortColumn {
+    const ALL: [SortColumn; 7] = [
+        SortColumn::Pid,
+        SortColumn::Name,
+        SortColumn::User,
+        SortColumn::Cpu,
+        SortColumn::Memory,
+        SortColumn::State,
+        SortColumn::Command,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            SortColumn::Pid => "PID",
+            SortColumn::Name => "Name",
+            SortColumn::User => "User",
+            SortColumn::Cpu => "CPU%",
+            SortColumn::Memory => "RSS",
+            SortColumn::State => "State",
+            SortColumn::Command => "Command",
+        }
+    }
+
+    fn compare(self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+        match self {
+            SortColumn::Pid => a.pid.as_u32().cmp(&b.pid.as_u32()),
+            SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortColumn::User => a.user.cmp(&b.user),
+            SortColumn::Cpu => a.cpu.total_cmp(&b.cpu),
+            SortColumn::Memory => a.memory.cmp(&b.memory),
+            SortColumn::State => a.state.cmp(&b.state),
+            SortColumn::Command => a.command.cmp(&b.command),
+        }
+    }
+}
+
+/// Linha exibida na tabela; no modo árvore carrega os totais da subárvore.
+struct DisplayRow<'a> {
+    process: &'a ProcessInfo,
+    prefix: String,
+    cpu: f32,
+    memory: u64,
+    has_children: bool,
+}
+
+impl<'a> DisplayRow<'a> {
+    fn flat(process: &'a ProcessInfo) -> Self {
+        Self {
+            process,
+            prefix: String::new(),
+            cpu: process.cpu,
+            memory: process.memory,
+            has_children: false,
+        }
+    }
+}
+
+struct Subtree {
+    cpu: f32,
+    memory: u64,
+    matched: bool,
+}
+
+static FLAT_WIDTHS: [Constraint; 7] = [
+    Constraint::Length(7),
+    Constraint::Length(16),
+    Constraint::Length(10),
+    Constraint::Length(7),
+    Constraint::Length(10),
+    Constraint::Length(9),
+    Constraint::Min(20),
+];
+
+static TREE_WIDTHS: [Constraint; 7] = [
+    Constraint::Length(7),
+    Constraint::Length(32),
+    Constraint::Length(10),
+    Constraint::Length(7),
+    Constraint::Length(10),
+    Constraint::Length(9),
+    Constraint::Min(20),
+];
+
+enum Mode {
+    Normal,
+    Filter,
+    Confirm(Pid, Signal),
+}
+
+fn signal_name(signal: Signal) -> &'static str {
+    match signal {
+        Signal::Kill => "SIGKILL",
+        _ => "SIGTERM",
+    }
+}
+
+/// Tabela de processos com ordenação, filtro e envio de sinais.
+pub struct ProcessTable {
+    rows: Vec<ProcessInfo>,
+    sort: SortColumn,
+    descending: bool,
+    filter: String,
+    tree: bool,
+    collapsed: HashSet<Pid>,
+    mode: Mode,
+    message: Option<String>,
+    pub state: TableState,
+}
+
+impl ProcessTable {
+    pub fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            sort: SortColumn::Cpu,
+            descending: true,
+            filter: String::new(),
+            tree: false,
+            collapsed: HashSet::new(),
+            mode: Mode::Normal,
+            message: None,
+            state: TableState::default(),
+        }
+    }
+
+    pub fn refresh(&mut self, sys: &System) {
+        // Manter a seleção no mesmo processo entre atualizações
+        let selected = self.selected().map(|process| process.pid);
+
+        self.rows = sys
+            .processes()
+            .iter()
+            .map(|(pid, process)| {
+                let command = process.cmd().join(" ");
+                ProcessInfo {
+                    pid: *pid,
+                    parent: process.parent(),
+                    name: process.name().to_string(),
+                    user: process
+                        .user_id()
+                        .and_then(|uid| sys.get_user_by_id(uid))
+                        .map(|user| user.name().to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    cpu: process.cpu_usage(),
+                    memory: process.memory(),
+                    state: process.status().to_string(),
+                    command: if command.is_empty() {
+                        process.name().to_string()
+                    } else {
+                        command
+                    },
+                }
+            })
+            .collect();
+        self.sort_rows();
+
+        let visible = self.visible();
+        let index = selected
+            .and_then(|pid| visible.iter().position(|row| row.process.pid == pid))
+            .or(if visible.is_empty() { None } else { Some(0) });
+        self.state.select(index);
+    }
+
+    fn sort_rows(&mut self) {
+        let (sort, descending) = (self.sort, self.descending);
+        self.rows.sort_by(|a, b| {
+            let ordering = sort.compare(a, b);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    fn visible(&self) -> Vec<DisplayRow<'_>> {
+        let filter = self.filter.to_lowercase();
+        if self.tree {
+            return self.tree_rows(&filter);
+        }
+        self.rows
+            .iter()
+            .filter(|process| process.matches(&filter))
+            .map(DisplayRow::flat)
+            .collect()
+    }
+
+    /// Agrupa os processos pelo PID do pai, somando CPU e memória de cada subárvore.
+    fn tree_rows(&self, filter: &str) -> Vec<DisplayRow<'_>> {
+        let index: HashMap<Pid, usize> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, process)| (process.pid, i))
+            .collect();
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); self.rows.len()];
+        let mut parents: Vec<Option<usize>> = vec![None; self.rows.len()];
+        let mut roots = Vec::new();
+        for (i, process) in self.rows.iter().enumerate() {
+            match process
+                .parent
+                .filter(|parent| *parent != process.pid)
+                .and_then(|parent| index.get(&parent))
+            {
+                Some(&parent) => {
+                    children[parent].push(i);
+                    parents[i] = Some(parent);
+                }
+                None => roots.push(i),
+            }
+        }
+
+        let mut subtrees: Vec<Option<Subtree>> = (0..self.rows.len()).map(|_| None).collect();
+        for &root in &roots {
+            self.subtree(root, &children, filter, &mut subtrees);
+        }
+        // Um PID reaproveitado entre duas leituras pode formar um ciclo de pais, que nenhuma
+        // raiz alcança: o processo sai da lista do pai (quebrando o ciclo) e vira raiz
+        for i in 0..self.rows.len() {
+            if subtrees[i].is_some() {
+                continue;
+            }
+            if let Some(parent) = parents[i] {
+                children[parent].retain(|&child| child != i);
+            }
+            roots.push(i);
+            self.subtree(i, &children, filter, &mut subtrees);
+        }
+        let subtree = |i: usize| subtrees[i].as_ref().expect("subtree computed from roots");
+
+        // CPU e memória são ordenadas pelos totais, as demais colunas pelo próprio processo
+        let order = |a: &usize, b: &usize| {
+            let ordering = match self.sort {
+                SortColumn::Cpu => subtree(*a).cpu.total_cmp(&subtree(*b).cpu),
+                SortColumn::Memory => subtree(*a).memory.cmp(&subtree(*b).memory),
+                column => column.compare(&self.rows[*a], &self.rows[*b]),
+            };
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        };
+        roots.sort_by(order);
+        for list in &mut children {
+            list.sort_by(order);
+        }
+
+        let mut output = Vec::new();
+        let mut stack: Vec<(usize, String, String)> = roots
+            .iter()
+            .rev()
+            .map(|&i| (i, String::new(), String::new()))
+            .collect();
+        while let Some((i, prefix, indent)) = stack.pop() {
+            let node = subtree(i);
+            if !node.matched {
+                continue;
+            }
+            let process = &self.rows[i];
+            let kids: Vec<usize> = children[i]
+                .iter()
+                .copied()
+                .filter(|&child| subtree(child).matched)
+                .collect();
+
+            if !self.collapsed.contains(&process.pid) {
+                for (n, &child) in kids.iter().enumerate().rev() {
+                    let last = n == kids.len() - 1;
+                    stack.push((
+                        child,
+                        format!("{}{}", indent, if last { "└─ " } else { "├─ " }),
+                        format!("{}{}", indent, if last { "   " } else { "│  " }),
+                    ));
+                }
+            }
+
+            output.push(DisplayRow {
+                process,
+                prefix,
+                cpu: node.cpu,
+                memory: node.memory,
+                has_children: !kids.is_empty(),
+            });
+        }
+        output
+    }
+
+    fn subtree(
+        &self,
+        i: usize,
+        children: &[Vec<usize>],
+        filter: &str,
+        subtrees: &mut Vec<Option<Subtree>>,
+    ) {
+        let process = &self.rows[i];
+        let mut total = Subtree {
+            cpu: process.cpu,
+            memory: process.memory,
+            matched: process.matches(filter),
+        };
+        for &child in &children[i] {
+            self.subtree(child, children, filter, subtrees);
+            if let Some(node) = &subtrees[child] {
+                total.cpu += node.cpu;
+                total.memory += node.memory;
+                total.matched |= node.matched;
+            }
+        }
+        subtrees[i] = Some(total);
+    }
+
+    fn selected(&self) -> Option<&ProcessInfo> {
+        let index = self.state.selected()?;
+        self.visible().get(index).map(|row| row.process)
+    }
+
+    fn set_collapsed(&mut self, collapsed: Option<bool>) {
+        if !self.tree {
+            return;
+        }
+        if let Some(pid) = self.selected().map(|process| process.pid) {
+            let collapse = collapsed.unwrap_or(!self.collapsed.contains(&pid));
+            if collapse {
+                self.collapsed.insert(pid);
+            } else {
+                self.collapsed.remove(&pid);
+            }
+        }
+        self.move_selection(0);
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.visible().len();
+        if len == 0 {
+            self.state.select(None);
+            return;
+        }
+        let current = self.state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, len as isize - 1);
+        self.state.select(Some(next as usize));
+    }
+
+    fn set_sort(&mut self, column: SortColumn) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            // Colunas numéricas começam do maior para o menor
+            self.descending = matches!(column, SortColumn::Cpu | SortColumn::Memory);
+        }
+        self.sort_rows();
+    }
+
+    fn send_signal(&mut self, sys: &System, pid: Pid, signal: Signal) {
+        self.message = Some(match sys.process(pid).map(|p| p.kill_with(signal)) {
+            Some(Some(true)) => format!("Sent {} to {}", signal_name(signal), pid),
+            Some(Some(false)) => format!("Failed to send {} to {}", signal_name(signal), pid),
+            Some(None) => format!("{} is not supported here", signal_name(signal)),
+            None => format!("Process {} no longer exists", pid),
+        });
+    }
+
+    /// Trata uma tecla; retorna `false` se ela não pertence à tabela.
+    pub fn handle_key(&mut self, key: KeyEvent, sys: &System) -> bool {
+        match self.mode {
+            Mode::Filter => {
+                match key.code {
+                    KeyCode::Char(c) => self.filter.push(c),
+                    KeyCode::Backspace => {
+                        self.filter.pop();
+                    }
+                    KeyCode::Esc => {
+                        self.filter.clear();
+                        self.mode = Mode::Normal;
+                    }
+                    KeyCode::Enter => self.mode = Mode::Normal,
+                    _ => {}
+                }
+                self.move_selection(0);
+                true
+            }
+            Mode::Confirm(pid, signal) => {
+                if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    self.send_signal(sys, pid, signal);
+                } else {
+                    self.message = Some("Cancelled".to_string());
+                }
+                self.mode = Mode::Normal;
+                true
+            }
+            Mode::Normal => {
+                match key.code {
+                    KeyCode::Up => self.move_selection(-1),
+                    KeyCode::Down => self.move_selection(1),
+                    KeyCode::PageUp => self.move_selection(-10),
+                    KeyCode::PageDown => self.move_selection(10),
+                    KeyCode::Home => self.move_selection(isize::MIN / 2),
+                    KeyCode::End => self.move_selection(isize::MAX / 2),
+                    KeyCode::Char('/') => {
+                        self.mode = Mode::Filter;
+                        self.message = None;
+                    }
+                    KeyCode::Char('v') => {
+                        self.tree = !self.tree;
+                        self.move_selection(0);
+                    }
+                    KeyCode::Left => self.set_collapsed(Some(true)),
+                    KeyCode::Right => self.set_collapsed(Some(false)),
+                    KeyCode::Char(' ') => self.set_collapsed(None),
+                    KeyCode::Char(c @ '1'..='7') => {
+                        self.set_sort(SortColumn::ALL[c as usize - '1' as usize])
+                    }
+                    KeyCode::Char('t') | KeyCode::Char('k') => {
+                        let signal = if key.code == KeyCode::Char('k') {
+                            Signal::Kill
+                        } else {
+                            Signal::Term
+                        };
+                        if let Some(process) = self.selected() {
+                            self.mode = Mode::Confirm(process.pid, signal);
+                        }
+                    }
+                    _ => return false,
+                }
+                true
+            }
+        }
+    }
+
+    fn title(&self) -> String {
+        match self.mode {
+            Mode::Filter => format!("Processes - filter: {}_", self.filter),
+            Mode::Confirm(pid, signal) => {
+                let name = self
+                    .rows
+                    .iter()
+                    .find(|process| process.pid == pid)
+                    .map(|process| process.name.as_str())
+                    .unwrap_or("?");
+                format!("Send {} to {} ({})? [y/N]", signal_name(signal), pid, name)
+            }
+            Mode::Normal => {
+                let mut title = format!(
+                    "{} ({}) - sort: {} {}",
+                    if self.tree {
+                        "Process Tree"
+                    } else {
+                        "Processes"
+                    },
+                    self.visible().len(),
+                    self.sort.title(),
+                    if self.descending { "▼" } else { "▲" }
+                );
+                if !self.filter.is_empty() {
+                    title.push_str(&format!(" - filter: {}", self.filter));
+                }
+                if let Some(message) = &self.message {
+                    title.push_str(&format!(" - {}", message));
+                }
+                title
+            }
+        }
+    }
+
+    pub fn widget(&self) -> Table<'static> {
+        let header = Row::new(SortColumn::ALL.iter().enumerate().map(|(i, column)| {
+            let style = if *column == self.sort {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Red)
+            };
+            Cell::from(format!("{} {}", i + 1, column.title())).style(style)
+        }));
+
+        let rows: Vec<Row> = self
+            .visible()
+            .into_iter()
+            .map(|row| {
+                let process = row.process;
+                let marker = if !row.has_children {
+                    ""
+                } else if self.collapsed.contains(&process.pid) {
+                    "[+] "
+                } else {
+                    "[-] "
+                };
+                Row::new(vec![
+                    Cell::from(process.pid.to_string()),
+                    Cell::from(format!("{}{}{}", row.prefix, marker, process.name)),
+                    Cell::from(process.user.clone()),
+                    Cell::from(format!("{:>5.1}", row.cpu)),
+                    Cell::from(format_size(row.memory, BINARY)),
+                    Cell::from(process.state.clone()),
+                    Cell::from(process.command.clone()),
+                ])
+            })
+            .collect();
+
+        let border = match self.mode {
+            Mode::Confirm(..) => Color::LightRed,
+            _ => Color::Red,
+        };
+
+        Table::new(rows)
+            .header(header)
+            .block(
+                Block::default()
+                    .title(Span::styled(
+                        self.title(),
+                        Style::default().fg(border).add_modifier(Modifier::BOLD),
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border)),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .widths(if self.tree {
+                &TREE_WIDTHS
+            } else {
+                &FLAT_WIDTHS
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, parent: Option<u32>, name: &str, cpu: f32, memory: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid: Pid::from_u32(pid),
+            parent: parent.map(Pid::from_u32),
+            name: name.to_string(),
+            user: "root".to_string(),
+            cpu,
+            memory,
+            state: "Sleeping".to_string(),
+            command: name.to_string(),
+        }
+    }
+
+    /// init(1) -> shell(2) -> make(3), cc(4); init(1) -> sshd(5)
+    fn tree_table() -> ProcessTable {
+        let mut table = ProcessTable::new();
+        table.rows = vec![
+            process(1, None, "init", 1.0, 100),
+            process(2, Some(1), "shell", 2.0, 200),
+            process(3, Some(2), "make", 10.0, 300),
+            process(4, Some(2), "cc", 40.0, 400),
+            process(5, Some(1), "sshd", 5.0, 500),
+        ];
+        table.tree = true;
+        table
+    }
+
+    fn rows(table: &ProcessTable) -> Vec<(u32, f32, u64)> {
+        table
+            .visible()
+            .iter()
+            .map(|row| (row.process.pid.as_u32(), row.cpu, row.memory))
+            .collect()
+    }
+
+    #[test]
+    fn tree_rows_sum_each_subtree() {
+        let table = tree_table();
+        // Ordenado por CPU total: shell (52) antes de sshd (5), cc antes de make
+        assert_eq!(
+            rows(&table),
+            [
+                (1, 58.0, 1500),
+                (2, 52.0, 900),
+                (4, 40.0, 400),
+                (3, 10.0, 300),
+                (5, 5.0, 500)
+            ]
+        );
+        let prefixes: Vec<String> = table
+            .visible()
+            .iter()
+            .map(|row| row.prefix.clone())
+            .collect();
+        assert_eq!(prefixes, ["", "├─ ", "│  ├─ ", "│  └─ ", "└─ "]);
+    }
+
+    #[test]
+    fn filter_keeps_the_ancestors_of_matches() {
+        let mut table = tree_table();
+        table.filter = "make".to_string();
+        let pids: Vec<u32> = rows(&table).iter().map(|row| row.0).collect();
+        assert_eq!(pids, [1, 2, 3]);
+        // Os totais continuam sendo da subárvore inteira
+        assert_eq!(rows(&table)[1], (2, 52.0, 900));
+    }
+
+    #[test]
+    fn collapsed_processes_hide_their_children() {
+        let mut table = tree_table();
+        table.collapsed.insert(Pid::from_u32(2));
+        assert_eq!(
+            rows(&table),
+            [(1, 58.0, 1500), (2, 52.0, 900), (5, 5.0, 500)]
+        );
+        assert!(table.visible()[1].has_children);
+    }
+
+    #[test]
+    fn parent_cycles_do_not_break_the_tree() {
+        let mut table = tree_table();
+        // 6 e 7 são pais um do outro (PID reaproveitado); 7 tem dois filhos
+        table.rows.extend([
+            process(6, Some(7), "old", 1.0, 10),
+            process(7, Some(6), "new", 1.0, 10),
+            process(8, Some(7), "a", 1.0, 10),
+            process(9, Some(7), "b", 2.0, 10),
+        ]);
+        let mut pids: Vec<u32> = rows(&table).iter().map(|row| row.0).collect();
+        pids.sort_unstable();
+        assert_eq!(pids, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+}
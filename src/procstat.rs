@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Contadores de tempo de uma linha `cpu` de /proc/stat, em jiffies.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+    pub guest: u64,
+    pub guest_nice: u64,
+}
+
+impl CpuTimes {
+    fn parse(fields: &[&str]) -> Option<CpuTimes> {
+        // Kernels antigos não têm as últimas colunas
+        let field = |i: usize| -> Option<u64> {
+            match fields.get(i) {
+                Some(value) => value.parse().ok(),
+                None => Some(0),
+            }
+        };
+        Some(CpuTimes {
+            user: field(0)?,
+            nice: field(1)?,
+            system: field(2)?,
+            idle: field(3)?,
+            iowait: field(4)?,
+            irq: field(5)?,
+            softirq: field(6)?,
+            steal: field(7)?,
+            guest: field(8)?,
+            guest_nice: field(9)?,
+        })
+    }
+
+    /// `user` e `nice` já incluem o tempo de guest, então ele não entra no total.
+    fn total(&self) -> u64 {
+        self.user
+            + self.nice
+            + self.system
+            + self.idle
+            + self.iowait
+            + self.irq
+            + self.softirq
+            + self.steal
+    }
+}
+
+/// Divisão do tempo de CPU entre uma leitura e outra, em % do total.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CpuBreakdown {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub idle: f64,
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub steal: f64,
+    pub guest: f64,
+}
+
+impl CpuBreakdown {
+    /// Estados na ordem em que são empilhados no gráfico (o ocioso fica de fora).
+    pub fn stacked(&self) -> [(&'static str, f64); 8] {
+        [
+            ("user", self.user),
+            ("nice", self.nice),
+            ("system", self.system),
+            ("irq", self.irq),
+            ("softirq", self.softirq),
+            ("iowait", self.iowait),
+            ("steal", self.steal),
+            ("guest", self.guest),
+        ]
+    }
+
+    fn between(previous: &CpuTimes, current: &CpuTimes) -> CpuBreakdown {
+        let total = current.total().saturating_sub(previous.total());
+        if total == 0 {
+            return CpuBreakdown::default();
+        }
+        let share =
+            |now: u64, before: u64| now.saturating_sub(before) as f64 * 100.0 / total as f64;
+        let guest = current.guest + current.guest_nice;
+        let previous_guest = previous.guest + previous.guest_nice;
+        CpuBreakdown {
+            // Guest sai de user/nice para não ser contado duas vezes
+            user: share(
+                current.user.saturating_sub(current.guest),
+                previous.user.saturating_sub(previous.guest),
+            ),
+            nice: share(
+                current.nice.saturating_sub(current.guest_nice),
+                previous.nice.saturating_sub(previous.guest_nice),
+            ),
+            system: share(current.system, previous.system),
+            idle: share(current.idle, previous.idle),
+            iowait: share(current.iowait, previous.iowait),
+            irq: share(current.irq, previous.irq),
+            softirq: share(current.softirq, previous.softirq),
+            steal: share(current.steal, previous.steal),
+            guest: share(guest, previous_guest),
+        }
+    }
+}
+
+/// O que interessa de /proc/stat: as linhas `cpu` e os contadores do kernel.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProcStat {
+    pub cpu: CpuTimes,
+    pub cpus: Vec<CpuTimes>,
+    pub context_switches: u64,
+    pub interrupts: u64,
+    /// Processos criados desde o boot (`processes`)
+    pub forks: u64,
+    pub procs_blocked: u64,
+}
+
+pub fn parse_stat(contents: &str) -> ProcStat {
+    let mut stat = ProcStat::default();
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(name) = fields.next() else {
+            continue;
+        };
+        let values: Vec<&str> = fields.collect();
+        let first = || values.first().and_then(|v| v.parse().ok()).unwrap_or(0);
+        if name == "ctxt" {
+            stat.context_switches = first();
+        } else if name == "intr" {
+            // O primeiro valor é o total; os outros são por IRQ
+            stat.interrupts = first();
+        } else if name == "processes" {
+            stat.forks = first();
+        } else if name == "procs_blocked" {
+            stat.procs_blocked = first();
+        } else if name == "cpu" {
+            stat.cpu = CpuTimes::parse(&values).unwrap_or_default();
+        } else if name.starts_with("cpu") {
+            if let Some(times) = CpuTimes::parse(&values) {
+                stat.cpus.push(times);
+            }
+        }
+    }
+    stat
+}
+
+/// Atividade do kernel por segundo, e tarefas no momento da leitura.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct KernelActivity {
+    pub context_switches: f64,
+    pub interrupts: f64,
+    pub forks: f64,
+    /// Tarefas executando agora e o total, de /proc/loadavg
+    pub running: u64,
+    pub total: u64,
+    /// Tarefas bloqueadas esperando I/O, de /proc/stat
+    pub blocked: u64,
+}
+
+/// Campo `executando/total` de /proc/loadavg (as médias vêm do sysinfo).
+pub fn parse_loadavg(contents: &str) -> Option<(u64, u64)> {
+    let (running, total) = contents.split_whitespace().nth(3)?.split_once('/')?;
+    Some((running.parse().ok()?, total.parse().ok()?))
+}
+
+/// Uma leitura de /proc/stat comparada com a anterior.
+pub struct StatSample {
+    pub total: CpuBreakdown,
+    pub cpus: Vec<CpuBreakdown>,
+    pub activity: KernelActivity,
+}
+
+/// Guarda a leitura anterior de /proc/stat para calcular a divisão do tempo e as taxas.
+#[derive(Default)]
+pub struct StatTracker {
+    previous: Option<(ProcStat, Instant)>,
+}
+
+impl StatTracker {
+    /// Diferença desde a última chamada; vazia na primeira e fora do Linux.
+    pub fn update(&mut self) -> Option<StatSample> {
+        let contents = std::fs::read_to_string("/proc/stat").ok()?;
+        let tasks = std::fs::read_to_string("/proc/loadavg")
+            .ok()
+            .and_then(|loadavg| parse_loadavg(&loadavg));
+        self.update_from(parse_stat(&contents), tasks, Instant::now())
+    }
+
+    fn update_from(
+        &mut self,
+        stat: ProcStat,
+        tasks: Option<(u64, u64)>,
+        now: Instant,
+    ) -> Option<StatSample> {
+        let (previous, then) = self.previous.replace((stat.clone(), now))?;
+        let seconds = now.duration_since(then).as_secs_f64();
+        let rate = |current: u64, before: u64| {
+            if seconds > 0.0 {
+                current.saturating_sub(before) as f64 / seconds
+            } else {
+                0.0
+            }
+        };
+        let (running, total) = tasks.unwrap_or_default();
+
+        Some(StatSample {
+            total: CpuBreakdown::between(&previous.cpu, &stat.cpu),
+            cpus: previous
+                .cpus
+                .iter()
+                .zip(&stat.cpus)
+                .map(|(before, now)| CpuBreakdown::between(before, now))
+                .collect(),
+            activity: KernelActivity {
+                context_switches: rate(stat.context_switches, previous.context_switches),
+                interrupts: rate(stat.interrupts, previous.interrupts),
+                forks: rate(stat.forks, previous.forks),
+                running,
+                total,
+                blocked: stat.procs_blocked,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaks_down_cpu_time() {
+        let mut tracker = StatTracker::default();
+        let start = Instant::now();
+        let before = "cpu  100 0 50 800 10 0 5 0 20 0\n\
+                      cpu0 50 0 25 400 5 0 5 0 20 0\n\
+                      cpu1 50 0 25 400 5 0 0 0 0 0\n\
+                      intr 12345 0 0\n\
+                      ctxt 1000\n\
+                      processes 500\n\
+                      procs_blocked 0\n";
+        // 200 jiffies: 60 de user (20 deles guest), 20 system, 40 iowait, 10 steal, 70 idle
+        let after = "cpu  160 0 70 870 50 0 5 10 40 0\n\
+                     cpu0 90 0 35 420 25 0 5 10 40 0\n\
+                     cpu1 70 0 35 450 25 0 0 0 0 0\n\
+                     intr 14345 3 4\n\
+                     ctxt 5000\n\
+                     processes 510\n\
+                     procs_blocked 3\n";
+        assert!(tracker
+            .update_from(parse_stat(before), None, start)
+            .is_none());
+
+        let sample = tracker
+            .update_from(
+                parse_stat(after),
+                parse_loadavg("0.52 0.58 0.59 2/1234 56789\n"),
+                start + std::time::Duration::from_secs(2),
+            )
+            .unwrap();
+        let (total, cpus) = (sample.total, sample.cpus);
+        assert_eq!(total.user, 20.0);
+        assert_eq!(total.guest, 10.0);
+        assert_eq!(total.system, 10.0);
+        assert_eq!(total.iowait, 20.0);
+        assert_eq!(total.steal, 5.0);
+        assert_eq!(total.idle, 35.0);
+        let sum: f64 = total.stacked().iter().map(|(_, share)| share).sum::<f64>() + total.idle;
+        assert_eq!(sum, 100.0);
+
+        assert_eq!(cpus.len(), 2);
+        assert_eq!(cpus[0].iowait, 20.0);
+        assert_eq!(cpus[1].idle, 50.0);
+
+        let activity = sample.activity;
+        assert_eq!(activity.context_switches, 2000.0);
+        assert_eq!(activity.interrupts, 1000.0);
+        assert_eq!(activity.forks, 5.0);
+        assert_eq!((activity.running, activity.total), (2, 1234));
+        assert_eq!(activity.blocked, 3);
+    }
+
+    #[test]
+    fn tolerates_old_kernels_without_steal_and_guest() {
+        let stat = parse_stat("cpu  1 2 3 4 5 6 7\n");
+        assert_eq!(stat.cpu.softirq, 7);
+        assert_eq!(stat.cpu.steal, 0);
+        assert!(stat.cpus.is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::sysfs::{read_number, read_string};
+
+/// Consumo da CPU em W; pacotes de vários sockets são somados.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CpuPower {
+    pub package: f64,
+    /// Só os núcleos, já incluído no pacote
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub core: Option<f64>,
+    /// Memória, fora do pacote
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dram: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Domain {
+    Package,
+    Core,
+    Dram,
+}
+
+/// Um domínio de `/sys/class/powercap/intel-rapl:N[:M]`.
+struct Zone {
+    domain: Domain,
+    path: PathBuf,
+    /// O contador volta a zero ao passar deste valor
+    max_range_uj: u64,
+    last_uj: u64,
+}
+
+/// Lê os contadores de energia RAPL (Intel e AMD) e converte em potência.
+pub struct RaplTracker {
+    zones: Vec<Zone>,
+    last: Instant,
+}
+
+impl RaplTracker {
+    /// Domínios de pacote, núcleos e DRAM; sem permissão de leitura (`energy_uj` é só do
+    /// root nos kernels recentes) o tracker fica vazio.
+    pub fn new(sysfs_root: &Path) -> Self {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(sysfs_root.join("class/powercap"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| {
+                        // `intel-rapl` sozinho é o tipo de controle, não um domínio
+                        entry
+                            .file_name()
+                            .to_string_lossy()
+                            .starts_with("intel-rapl:")
+                    })
+                    .map(|entry| entry.path())
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+
+        let zones = paths
+            .into_iter()
+            .filter_map(|path| {
+                let domain = match read_string(&path.join("name"))?.as_str() {
+                    name if name.starts_with("package") => Domain::Package,
+                    "core" => Domain::Core,
+                    "dram" => Domain::Dram,
+                    // uncore (GPU integrada) e psys ficam de fora
+                    _ => return None,
+                };
+                Some(Zone {
+                    domain,
+                    last_uj: read_number(&path.join("energy_uj"))?,
+                    max_range_uj: read_number(&path.join("max_energy_range_uj"))
+                        .unwrap_or(u64::MAX),
+                    path,
+                })
+            })
+            .collect();
+
+        Self {
+            zones,
+            last: Instant::now(),
+        }
+    }
+
+    /// Potência média desde a última leitura e a energia consumida no intervalo, em J.
+    pub fn sample(&mut self) -> Option<(CpuPower, f64)> {
+        self.sample_at(Instant::now())
+    }
+
+    fn sample_at(&mut self, now: Instant) -> Option<(CpuPower, f64)> {
+        let seconds = now.duration_since(self.last).as_secs_f64();
+        if self.zones.is_empty() || seconds <= 0.0 {
+            return None;
+        }
+        self.last = now;
+
+        let mut power = CpuPower::default();
+        let mut joules = 0.0;
+        for zone in &mut self.zones {
+            let Some(current) = read_number(&zone.path.join("energy_uj")) else {
+                continue;
+            };
+            let delta = energy_delta(zone.last_uj, current, zone.max_range_uj) as f64 / 1e6;
+            zone.last_uj = current;
+
+            let watts = delta / seconds;
+            match zone.domain {
+                Domain::Package => {
+                    power.package += watts;
+                    joules += delta;
+                }
+                Domain::Core => *power.core.get_or_insert(0.0) += watts,
+                Domain::Dram => {
+                    *power.dram.get_or_insert(0.0) += watts;
+                    joules += delta;
+                }
+            }
+        }
+        Some((power, joules))
+    }
+}
+
+/// Diferença entre duas leituras de um contador que volta a zero depois de `max_range`.
+fn energy_delta(previous: u64, current: u64, max_range: u64) -> u64 {
+    if current >= previous {
+        current - previous
+    } else {
+        max_range.saturating_sub(previous) + current
+    }
+}
+
+/// Energia acumulada desde que o monitor abriu.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionEnergy {
+    /// Pacote + DRAM, dos contadores RAPL
+    pub cpu_joules: f64,
+    /// Integral do consumo informado pelas GPUs
+    pub gpu_joules: f64,
+}
+
+impl SessionEnergy {
+    pub fn total_joules(&self) -> f64 {
+        self.cpu_joules + self.gpu_joules
+    }
+}
+
+/// `12.3 kJ (3.42 Wh)`
+pub fn format_energy(joules: f64) -> String {
+    let energy = if joules >= 1e6 {
+        format!("{:.2} MJ", joules / 1e6)
+    } else if joules >= 1e3 {
+        format!("{:.1} kJ", joules / 1e3)
+    } else {
+        format!("{:.0} J", joules)
+    };
+    format!("{} ({:.2} Wh)", energy, joules / 3600.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysfs;
+    use std::time::Duration;
+
+    #[test]
+    fn counter_wraparound() {
+        assert_eq!(energy_delta(100, 250, 1000), 150);
+        assert_eq!(energy_delta(900, 50, 1000), 150);
+    }
+
+    #[test]
+    fn reads_package_core_and_dram_power() {
+        let zone = |path: &str, name: &str, energy: &str| {
+            [
+                (
+                    format!("class/powercap/{}/name", path),
+                    format!("{}\n", name),
+                ),
+                (
+                    format!("class/powercap/{}/energy_uj", path),
+                    format!("{}\n", energy),
+                ),
+                (
+                    format!("class/powercap/{}/max_energy_range_uj", path),
+                    "262143328850\n".to_string(),
+                ),
+            ]
+        };
+        let files: Vec<(String, String)> = [
+            zone("intel-rapl:0", "package-0", "1000000"),
+            zone("intel-rapl:0:0", "core", "500000"),
+            zone("intel-rapl:0:1", "uncore", "0"),
+            zone("intel-rapl:0:2", "dram", "262143000000"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let files: Vec<(&str, &str)> = files
+            .iter()
+            .map(|(path, contents)| (path.as_str(), contents.as_str()))
+            .collect();
+        let root = sysfs::fixture("rapl", &files);
+        // O diretório do tipo de controle não é um domínio
+        sysfs::write_fixture(&root, "class/powercap/intel-rapl/enabled", "1\n");
+
+        let mut rapl = RaplTracker::new(&root);
+        assert_eq!(rapl.zones.len(), 3);
+
+        sysfs::write_fixture(&root, "class/powercap/intel-rapl:0/energy_uj", "21000000\n");
+        sysfs::write_fixture(
+            &root,
+            "class/powercap/intel-rapl:0:0/energy_uj",
+            "15500000\n",
+        );
+        // A DRAM deu a volta: 328850 até o fim e mais 1671150
+        sysfs::write_fixture(
+            &root,
+            "class/powercap/intel-rapl:0:2/energy_uj",
+            "1671150\n",
+        );
+
+        let start = rapl.last;
+        let (power, joules) = rapl.sample_at(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(power.package, 10.0);
+        assert_eq!(power.core, Some(7.5));
+        assert_eq!(power.dram, Some(1.0));
+        assert_eq!(joules, 22.0);
+    }
+
+    #[test]
+    fn formats_energy() {
+        assert_eq!(format_energy(360.0), "360 J (0.10 Wh)");
+        assert_eq!(format_energy(12_345.0), "12.3 kJ (3.43 Wh)");
+    }
+}
@@ -0,0 +1,332 @@
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::hardware::SystemInfo;
+use crate::snapshot::Snapshot;
+
+/// Cabeçalho fixo do CSV; novas colunas só podem ser adicionadas no final.
+pub const CSV_HEADER: &str = "timestamp,timestamp_ms,cpu_usage,\
+mem_total,mem_used,mem_percent,swap_total,swap_used,swap_percent,\
+gpu_usage,gpu_memory_percent,gpu_temperature,\
+net_rx_rate,net_tx_rate,net_rx_total,net_tx_total";
+
+pub fn csv_row(snapshot: &Snapshot) -> String {
+    // As colunas de GPU são da primeira placa; o JSON traz todas
+    let (gpu_usage, gpu_memory, gpu_temp) = match snapshot.gpus.first() {
+        Some(gpu) => (
+            format!("{:.2}", gpu.usage),
+            format!("{:.2}", gpu.memory_percent),
+            gpu.temperature
+                .map(|t| format!("{:.1}", t))
+                .unwrap_or_default(),
+        ),
+        None => Default::default(),
+    };
+    let (rx_rate, tx_rate, rx_total, tx_total) = match &snapshot.network {
+        Some(network) => (
+            format!("{:.0}", network.rx_rate),
+            format!("{:.0}", network.tx_rate),
+            network.rx_total.to_string(),
+            network.tx_total.to_string(),
+        ),
+        None => Default::default(),
+    };
+
+    [
+        snapshot.timestamp.clone(),
+        snapshot.timestamp_ms.to_string(),
+        format!("{:.2}", snapshot.cpu.usage),
+        snapshot.memory.total.to_string(),
+        snapshot.memory.used.to_string(),
+        format!("{:.2}", snapshot.memory.percent),
+        snapshot.swap.total.to_string(),
+        snapshot.swap.used.to_string(),
+        format!("{:.2}", snapshot.swap.percent),
+        gpu_usage,
+        gpu_memory,
+        gpu_temp,
+        rx_rate,
+        tx_rate,
+        rx_total,
+        tx_total,
+    ]
+    .join(",")
+}
+
+/// Grava amostras em CSV, rotacionando o arquivo por tamanho e/ou por dia.
+pub struct CsvRecorder {
+    path: PathBuf,
+    max_bytes: Option<u64>,
+    daily: bool,
+    file: Option<File>,
+    day: Option<NaiveDate>,
+    written: u64,
+}
+
+impl CsvRecorder {
+    pub fn new(path: PathBuf, max_bytes: Option<u64>, daily: bool) -> io::Result<Self> {
+        let mut recorder = Self {
+            path,
+            max_bytes,
+            daily,
+            file: None,
+            day: None,
+            written: 0,
+        };
+        // Abrir já na criação para que erros de caminho apareçam na inicialização
+        recorder.open(Local::now().date_naive())?;
+        Ok(recorder)
+    }
+
+    /// Caminho do arquivo ativo; no modo diário a data entra no nome.
+    fn active_path(&self, day: NaiveDate) -> PathBuf {
+        if self.daily {
+            with_suffix(&self.path, &day.format("%Y-%m-%d").to_string())
+        } else {
+            self.path.clone()
+        }
+    }
+
+    fn open(&mut self, day: NaiveDate) -> io::Result<()> {
+        let path = self.active_path(day);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.written = file.metadata()?.len();
+        if self.written == 0 {
+            writeln!(file, "{}", CSV_HEADER)?;
+            self.written = CSV_HEADER.len() as u64 + 1;
+        }
+        self.file = Some(file);
+        self.day = Some(day);
+        Ok(())
+    }
+
+    /// Renomeia o arquivo cheio para `<nome>-<horário>.csv` e começa outro.
+    fn rotate_by_size(&mut self, now: DateTime<Local>) -> io::Result<()> {
+        self.file = None;
+        let day = now.date_naive();
+        let path = self.active_path(day);
+        let stamp = now.format("%Y%m%d-%H%M%S").to_string();
+        // Mais de uma rotação no mesmo segundo ganha um número, sem sobrescrever a anterior
+        let mut rotated = with_suffix(&path, &stamp);
+        let mut sequence = 1;
+        while rotated.exists() {
+            rotated = with_suffix(&path, &format!("{}-{}", stamp, sequence));
+            sequence += 1;
+        }
+        fs::rename(&path, rotated)?;
+        self.open(day)
+    }
+
+    pub fn record(&mut self, snapshot: &Snapshot) -> io::Result<()> {
+        self.record_at(snapshot, Local::now())
+    }
+
+    fn record_at(&mut self, snapshot: &Snapshot, now: DateTime<Local>) -> io::Result<()> {
+        let today = now.date_naive();
+        if self.daily && self.day != Some(today) {
+            self.open(today)?;
+        }
+        // Um arquivo só com o cabeçalho não é rotacionado
+        let has_rows = self.written > CSV_HEADER.len() as u64 + 1;
+        if has_rows && matches!(self.max_bytes, Some(max) if self.written >= max) {
+            self.rotate_by_size(now)?;
+        }
+
+        let row = csv_row(snapshot);
+        if let Some(file) = self.file.as_mut() {
+            writeln!(file, "{}", row)?;
+            self.written += row.len() as u64 + 1;
+        }
+        Ok(())
+    }
+}
+
+/// `dir/samples.csv` + `x` -> `dir/samples-x.csv`
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, suffix, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, suffix),
+    };
+    path.with_file_name(name)
+}
+
+/// Cabeçalho de uma sessão gravada, na primeira linha do arquivo.
+#[derive(Serialize, Deserialize)]
+pub struct SessionHeader {
+    pub monitor_session: u32,
+    pub system: SystemInfo,
+}
+
+pub const SESSION_VERSION: u32 = 1;
+
+/// Grava a sessão como JSON por linha: o cabeçalho e depois uma amostra por linha.
+pub struct SessionRecorder {
+    file: BufWriter<File>,
+}
+
+impl SessionRecorder {
+    pub fn new(path: &Path, system: SystemInfo) -> io::Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = BufWriter::new(File::create(path)?);
+        let header = SessionHeader {
+            monitor_session: SESSION_VERSION,
+            system,
+        };
+        writeln!(file, "{}", serde_json::to_string(&header)?)?;
+        file.flush()?;
+        Ok(Self { file })
+    }
+
+    pub fn record(&mut self, snapshot: &Snapshot) -> io::Result<()> {
+        writeln!(self.file, "{}", serde_json::to_string(snapshot)?)?;
+        self.file.flush()
+    }
+}
+
+/// Destino de `--record`, escolhido pela extensão do arquivo.
+pub enum Recorder {
+    Csv(CsvRecorder),
+    Session(SessionRecorder),
+}
+
+impl Recorder {
+    pub fn new(
+        path: PathBuf,
+        max_bytes: Option<u64>,
+        daily: bool,
+        system: &SystemInfo,
+    ) -> io::Result<Self> {
+        let is_csv = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        if is_csv {
+            Ok(Recorder::Csv(CsvRecorder::new(path, max_bytes, daily)?))
+        } else {
+            Ok(Recorder::Session(SessionRecorder::new(
+                &path,
+                system.clone(),
+            )?))
+        }
+    }
+
+    pub fn record(&mut self, snapshot: &Snapshot) -> io::Result<()> {
+        match self {
+            Recorder::Csv(recorder) => recorder.record(snapshot),
+            Recorder::Session(recorder) => recorder.record(snapshot),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sample(usage: f64) -> Snapshot {
+        serde_json::from_str(&format!(
+            r#"{{"timestamp": "2024-01-01T10:00:00+00:00", "timestamp_ms": 1704103200000,
+                "cpu": {{"usage": {}, "cores": []}},
+                "memory": {{"total": 8000, "used": 2000, "percent": 25.0}},
+                "swap": {{"total": 0, "used": 0, "percent": 0.0}},
+                "network": {{"rx_rate": 1500.4, "tx_rate": 20.0, "rx_total": 10, "tx_total": 20,
+                            "interfaces": []}}}}"#,
+            usage
+        ))
+        .unwrap()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("monitor-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn files(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn csv_rows_follow_the_header() {
+        let header: Vec<&str> = CSV_HEADER.split(',').collect();
+        assert_eq!(header.len(), 16);
+        assert_eq!(header[..3], ["timestamp", "timestamp_ms", "cpu_usage"]);
+        assert_eq!(
+            header[12..],
+            ["net_rx_rate", "net_tx_rate", "net_rx_total", "net_tx_total"]
+        );
+
+        let row = csv_row(&sample(42.5));
+        let columns: Vec<&str> = row.split(',').collect();
+        assert_eq!(columns.len(), header.len());
+        let column = |name: &str| columns[header.iter().position(|h| *h == name).unwrap()];
+        assert_eq!(column("cpu_usage"), "42.50");
+        assert_eq!(column("mem_percent"), "25.00");
+        // Sem GPU as colunas ficam vazias, mas continuam no lugar
+        assert_eq!(column("gpu_usage"), "");
+        assert_eq!(column("gpu_temperature"), "");
+        assert_eq!(column("net_rx_rate"), "1500");
+        assert_eq!(column("net_tx_total"), "20");
+    }
+
+    #[test]
+    fn rotates_by_size_without_overwriting() {
+        let dir = temp_dir("record-size");
+        let mut recorder = CsvRecorder::new(dir.join("samples.csv"), Some(1), false).unwrap();
+        let now = Local.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        // Todas no mesmo segundo: cada rotação precisa de um nome próprio
+        for usage in [1.0, 2.0, 3.0] {
+            recorder.record_at(&sample(usage), now).unwrap();
+        }
+        assert_eq!(
+            files(&dir),
+            [
+                "samples-20240101-100000-1.csv",
+                "samples-20240101-100000.csv",
+                "samples.csv"
+            ]
+        );
+        // Cada arquivo tem o cabeçalho e uma linha
+        for name in files(&dir) {
+            let contents = fs::read_to_string(dir.join(name)).unwrap();
+            let lines: Vec<&str> = contents.lines().collect();
+            assert_eq!(lines.len(), 2);
+            assert_eq!(lines[0], CSV_HEADER);
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn starts_a_file_per_day() {
+        let dir = temp_dir("record-daily");
+        let mut recorder = CsvRecorder::new(dir.join("samples.csv"), None, true).unwrap();
+        let first = Local.with_ymd_and_hms(2024, 1, 1, 23, 59, 59).unwrap();
+        let second = Local.with_ymd_and_hms(2024, 1, 2, 0, 0, 1).unwrap();
+        recorder.record_at(&sample(1.0), first).unwrap();
+        recorder.record_at(&sample(2.0), second).unwrap();
+        recorder.record_at(&sample(3.0), second).unwrap();
+
+        let names = files(&dir);
+        assert!(names.contains(&"samples-2024-01-01.csv".to_string()));
+        let lines = |name: &str| fs::read_to_string(dir.join(name)).unwrap().lines().count();
+        assert_eq!(lines("samples-2024-01-01.csv"), 2);
+        assert_eq!(lines("samples-2024-01-02.csv"), 3);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
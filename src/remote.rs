@@ -0,0 +1,498 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::hardware::SystemInfo;
+use crate::record::{SessionHeader, SESSION_VERSION};
+use crate::snapshot::Snapshot;
+
+/// Versão do handshake; depois dele o fluxo é igual a uma sessão gravada com `--record`.
+const PROTOCOL_VERSION: u32 = 1;
+// Amostras guardadas para um cliente lento antes de começar a descartar
+const CLIENT_BACKLOG: usize = 64;
+// Tamanho máximo da linha do hello, que chega antes de o token ser conferido
+const MAX_HELLO: u64 = 4096;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+// Sem nenhuma linha nesse tempo a conexão é dada como perdida
+const READ_TIMEOUT: Duration = Duration::from_secs(15);
+// Linha vazia enviada ao cliente quando o intervalo do agente é maior que isso
+const KEEPALIVE: Duration = Duration::from_secs(5);
+const RECONNECT_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(10);
+/// Sem amostras por esse tempo, os dados na tela são marcados como antigos.
+pub const STALE_AFTER: Duration = Duration::from_secs(5);
+
+/// Primeira linha enviada pelo cliente.
+#[derive(Serialize, Deserialize)]
+struct Hello {
+    monitor_agent: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+}
+
+/// Resposta do agente ao handshake: o cabeçalho da sessão ou o motivo da recusa.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Reply {
+    Refused { error: String },
+    Accepted(SessionHeader),
+}
+
+/// Clientes conectados e a última amostra, que quem acaba de conectar recebe logo.
+#[derive(Default)]
+struct Clients {
+    senders: Vec<SyncSender<Arc<str>>>,
+    latest: Option<Arc<str>>,
+}
+
+/// `monitor agent`: aceita clientes e repassa a cada um as amostras publicadas.
+pub struct AgentServer {
+    clients: Arc<Mutex<Clients>>,
+    address: SocketAddr,
+}
+
+impl AgentServer {
+    pub fn start(
+        address: SocketAddr,
+        token: Option<String>,
+        system: SystemInfo,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let clients = Arc::new(Mutex::new(Clients::default()));
+
+        let shared = Arc::clone(&clients);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (clients, token, system) = (Arc::clone(&shared), token.clone(), system.clone());
+                // Uma thread por cliente, para que um link lento não atrase os outros
+                thread::spawn(move || {
+                    let _ = serve(stream, token.as_deref(), system, &clients);
+                });
+            }
+        });
+
+        Ok(Self { clients, address })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn publish(&self, snapshot: &Snapshot) {
+        let Ok(json) = serde_json::to_string(snapshot) else {
+            return;
+        };
+        let line: Arc<str> = format!("{}\n", json).into();
+        if let Ok(mut clients) = self.clients.lock() {
+            // Fila cheia descarta a amostra para esse cliente; desconectado sai da lista
+            clients.senders.retain(|client| {
+                !matches!(
+                    client.try_send(Arc::clone(&line)),
+                    Err(TrySendError::Disconnected(_))
+                )
+            });
+            clients.latest = Some(line);
+        }
+    }
+}
+
+fn serve(
+    stream: TcpStream,
+    token: Option<&str>,
+    system: SystemInfo,
+    clients: &Mutex<Clients>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    stream.set_nodelay(true)?;
+    let mut writer = stream.try_clone()?;
+
+    let mut line = String::new();
+    BufReader::new((&stream).take(MAX_HELLO)).read_line(&mut line)?;
+    let refusal = match serde_json::from_str::<Hello>(&line) {
+        _ if line.len() as u64 >= MAX_HELLO => Some("hello line too long".to_string()),
+        Err(_) => Some("expected a monitor client".to_string()),
+        Ok(hello) if hello.monitor_agent != PROTOCOL_VERSION => Some(format!(
+            "unsupported protocol version {}",
+            hello.monitor_agent
+        )),
+        Ok(hello) if token.is_some() && hello.token.as_deref() != token => {
+            Some("invalid token".to_string())
+        }
+        Ok(_) => None,
+    };
+    if let Some(error) = refusal {
+        writeln!(
+            writer,
+            "{}",
+            serde_json::to_string(&Reply::Refused { error })?
+        )?;
+        return Ok(());
+    }
+
+    // Registrar antes do cabeçalho: quem recebeu o cabeçalho já recebe as próximas amostras,
+    // começando pela última publicada (com intervalos longos a próxima pode demorar)
+    let (sender, samples) = mpsc::sync_channel(CLIENT_BACKLOG);
+    if let Ok(mut clients) = clients.lock() {
+        if let Some(latest) = &clients.latest {
+            let _ = sender.try_send(Arc::clone(latest));
+        }
+        clients.senders.push(sender);
+    }
+    let header = SessionHeader {
+        monitor_session: SESSION_VERSION,
+        system,
+    };
+    writeln!(
+        writer,
+        "{}",
+        serde_json::to_string(&Reply::Accepted(header))?
+    )?;
+    loop {
+        match samples.recv_timeout(KEEPALIVE) {
+            Ok(line) => writer.write_all(line.as_bytes())?,
+            // Mantém o cliente abaixo de READ_TIMEOUT mesmo sem amostras novas
+            Err(RecvTimeoutError::Timeout) => writer.write_all(b"\n")?,
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+    }
+}
+
+/// Abre uma conexão com o agente e faz o handshake; devolve o hardware remoto e o leitor.
+fn connect(address: &str, token: Option<&str>) -> io::Result<(SystemInfo, BufReader<TcpStream>)> {
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "address not found");
+    let stream = address
+        .to_socket_addrs()?
+        .find_map(
+            |address| match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+                Ok(stream) => Some(stream),
+                Err(e) => {
+                    last_error = e;
+                    None
+                }
+            },
+        )
+        .ok_or(last_error)?;
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let hello = Hello {
+        monitor_agent: PROTOCOL_VERSION,
+        token: token.map(str::to_string),
+    };
+    writeln!(&stream, "{}", serde_json::to_string(&hello)?)?;
+
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "connection closed during handshake",
+        ));
+    }
+    match serde_json::from_str(&line)? {
+        Reply::Accepted(header) => Ok((header.system, reader)),
+        Reply::Refused { error } => Err(io::Error::new(io::ErrorKind::PermissionDenied, error)),
+    }
+}
+
+enum RemoteEvent {
+    Connected(SystemInfo),
+    Sample(Box<Snapshot>),
+    Disconnected(String),
+}
+
+/// Recebe amostras de um agente numa thread própria, reconectando quando o link cai.
+pub struct RemoteClient {
+    pub address: String,
+    events: Receiver<RemoteEvent>,
+    system: Option<SystemInfo>,
+    // O hardware mudou desde o último `take_system`
+    system_changed: bool,
+    connected: bool,
+    error: Option<String>,
+    last_sample: Option<Instant>,
+    // Últimas amostras recebidas, até `history` (o `monitor fleet` desenha com elas)
+    recent: VecDeque<Snapshot>,
+    history: usize,
+}
+
+impl RemoteClient {
+    pub fn start(address: String, token: Option<String>, history: usize) -> Self {
+        let (sender, events) = mpsc::channel();
+        let target = address.clone();
+        thread::spawn(move || run(&target, token.as_deref(), &sender));
+        Self {
+            address,
+            events,
+            system: None,
+            system_changed: false,
+            connected: false,
+            error: None,
+            last_sample: None,
+            recent: VecDeque::with_capacity(history),
+            history,
+        }
+    }
+
+    fn handle(&mut self, event: RemoteEvent) -> Option<Snapshot> {
+        match event {
+            RemoteEvent::Connected(system) => {
+                self.system = Some(system);
+                self.system_changed = true;
+                self.connected = true;
+                self.error = None;
+            }
+            RemoteEvent::Sample(sample) => {
+                self.last_sample = Some(Instant::now());
+                if self.history > 0 {
+                    if self.recent.len() == self.history {
+                        self.recent.pop_front();
+                    }
+                    self.recent.push_back((*sample).clone());
+                }
+                return Some(*sample);
+            }
+            RemoteEvent::Disconnected(error) => {
+                self.connected = false;
+                self.error = Some(error);
+            }
+        }
+        None
+    }
+
+    /// Amostras que chegaram desde a última chamada, em ordem.
+    pub fn poll(&mut self) -> Vec<Snapshot> {
+        let mut samples = Vec::new();
+        while let Ok(event) = self.events.try_recv() {
+            samples.extend(self.handle(event));
+        }
+        samples
+    }
+
+    /// Espera a primeira amostra; um erro antes dela (endereço errado, token recusado) é devolvido.
+    pub fn wait_for_sample(&mut self, timeout: Duration) -> Result<Snapshot, String> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.events.recv_timeout(remaining) {
+                Ok(RemoteEvent::Disconnected(error)) => {
+                    self.handle(RemoteEvent::Disconnected(error.clone()));
+                    return Err(error);
+                }
+                Ok(event) => {
+                    if let Some(sample) = self.handle(event) {
+                        return Ok(sample);
+                    }
+                }
+                Err(_) => return Err("timed out waiting for a sample".to_string()),
+            }
+        }
+    }
+
+    /// Hardware informado na conexão mais recente (só uma vez por conexão).
+    pub fn take_system(&mut self) -> Option<SystemInfo> {
+        if !self.system_changed {
+            return None;
+        }
+        self.system_changed = false;
+        self.system.clone()
+    }
+
+    pub fn system(&self) -> Option<&SystemInfo> {
+        self.system.as_ref()
+    }
+
+    /// As últimas amostras, da mais antiga para a mais recente.
+    pub fn recent(&self) -> &VecDeque<Snapshot> {
+        &self.recent
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Tempo desde a última amostra, se alguma já chegou.
+    pub fn sample_age(&self) -> Option<Duration> {
+        self.last_sample.map(|last| last.elapsed())
+    }
+
+    pub fn is_stale(&self) -> bool {
+        match self.last_sample {
+            Some(last) => last.elapsed() > STALE_AFTER,
+            None => true,
+        }
+    }
+
+    /// Texto da linha de status: o endereço, e há quanto tempo os dados pararam.
+    pub fn status(&self) -> String {
+        if !self.is_stale() {
+            return format!("⇄ {}", self.address);
+        }
+        let mut status = match self.last_sample {
+            Some(last) => format!("⚠ {} STALE {}s", self.address, last.elapsed().as_secs()),
+            None => format!("⚠ {} waiting", self.address),
+        };
+        if let Some(error) = &self.error {
+            status.push_str(&format!(" (reconnecting: {})", error));
+        }
+        status
+    }
+}
+
+fn run(address: &str, token: Option<&str>, events: &Sender<RemoteEvent>) {
+    let mut delay = RECONNECT_MIN;
+    loop {
+        let error = match connect(address, token) {
+            Ok((system, reader)) => {
+                delay = RECONNECT_MIN;
+                if events.send(RemoteEvent::Connected(system)).is_err() {
+                    return;
+                }
+                match forward(reader, events) {
+                    Ok(()) => return,
+                    Err(e) => e,
+                }
+            }
+            Err(e) => e,
+        };
+        // O cliente foi descartado: nada mais a fazer
+        if events
+            .send(RemoteEvent::Disconnected(error.to_string()))
+            .is_err()
+        {
+            return;
+        }
+        thread::sleep(delay);
+        delay = (delay * 2).min(RECONNECT_MAX);
+    }
+}
+
+/// Repassa as amostras até a conexão cair; `Ok` quando o cliente não quer mais eventos.
+fn forward(reader: BufReader<TcpStream>, events: &Sender<RemoteEvent>) -> io::Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        // Linhas vazias são só keepalive do agente
+        if line.trim().is_empty() {
+            continue;
+        }
+        let sample: Snapshot = serde_json::from_str(&line)?;
+        if events.send(RemoteEvent::Sample(Box::new(sample))).is_err() {
+            return Ok(());
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "connection closed by the agent",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system() -> SystemInfo {
+        SystemInfo {
+            cpu_model: "Remote CPU".to_string(),
+            cpu_cores: 4,
+            cpu_threads: 8,
+            gpu_model: "None".to_string(),
+            os_name: "Linux".to_string(),
+            os_version: "6.1".to_string(),
+        }
+    }
+
+    fn sample(usage: f64) -> Snapshot {
+        let memory = r#"{"total": 8000, "used": 2000, "available": 6000, "percent": 25.0}"#;
+        serde_json::from_str(&format!(
+            r#"{{"timestamp": "2024-01-01T00:00:00+00:00", "timestamp_ms": 1704067200000,
+                "cpu": {{"usage": {}, "cores": [10.0]}}, "memory": {}, "swap": {}}}"#,
+            usage, memory, memory
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn streams_samples_to_clients_with_the_token() {
+        let agent = AgentServer::start(
+            "127.0.0.1:0".parse().unwrap(),
+            Some("secret".to_string()),
+            system(),
+        )
+        .unwrap();
+        let address = agent.local_addr().to_string();
+
+        let refused = connect(&address, Some("wrong")).err().unwrap();
+        assert_eq!(refused.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(refused.to_string(), "invalid token");
+        assert!(connect(&address, None).is_err());
+
+        let mut client = RemoteClient::start(address, Some("secret".to_string()), 2);
+        assert!(client.is_stale());
+        // O cliente conecta em segundo plano; publicar até a primeira amostra chegar
+        let received = (0..50)
+            .find_map(|_| {
+                agent.publish(&sample(42.5));
+                client.wait_for_sample(Duration::from_millis(100)).ok()
+            })
+            .expect("sample from the agent");
+        assert_eq!(received.cpu.usage, 42.5);
+        assert!(!client.is_stale());
+        assert_eq!(client.take_system().unwrap().cpu_model, "Remote CPU");
+        assert!(client.take_system().is_none());
+        assert!(client.status().starts_with("⇄ 127.0.0.1:"));
+
+        agent.publish(&sample(50.0));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut usage = Vec::new();
+        while !usage.contains(&50.0) && Instant::now() < deadline {
+            usage.extend(client.poll().iter().map(|sample| sample.cpu.usage));
+        }
+        assert_eq!(usage.last(), Some(&50.0));
+        // Só as últimas `history` amostras ficam guardadas
+        assert_eq!(client.recent().len(), 2);
+        assert_eq!(client.recent().back().unwrap().cpu.usage, 50.0);
+    }
+
+    #[test]
+    fn refuses_oversized_hello_lines() {
+        let agent = AgentServer::start("127.0.0.1:0".parse().unwrap(), None, system()).unwrap();
+        let mut stream = TcpStream::connect(agent.local_addr()).unwrap();
+        stream.write_all(&[b'a'; MAX_HELLO as usize]).unwrap();
+
+        let mut reply = String::new();
+        BufReader::new(stream).read_line(&mut reply).unwrap();
+        assert!(reply.contains("hello line too long"), "{}", reply);
+    }
+
+    #[test]
+    fn new_clients_get_the_latest_sample_right_away() {
+        let agent = AgentServer::start("127.0.0.1:0".parse().unwrap(), None, system()).unwrap();
+        agent.publish(&sample(10.0));
+        agent.publish(&sample(20.0));
+
+        let mut client = RemoteClient::start(agent.local_addr().to_string(), None, 0);
+        let received = client.wait_for_sample(Duration::from_secs(5)).unwrap();
+        assert_eq!(received.cpu.usage, 20.0);
+    }
+
+    #[test]
+    fn reports_unreachable_agents() {
+        // Porta fechada: o bind libera a porta logo em seguida
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        let mut client = RemoteClient::start(address, None, 0);
+        assert!(client.wait_for_sample(Duration::from_secs(5)).is_err());
+        assert!(!client.is_connected());
+        assert!(client.recent().is_empty());
+        assert!(client.status().contains("reconnecting"));
+    }
+}
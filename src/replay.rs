@@ -0,0 +1,287 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::hardware::SystemInfo;
+use crate::record::SessionHeader;
+use crate::snapshot::Snapshot;
+use crate::{AppConfig, SystemData};
+
+// Quanto cada seta avança ou volta no replay
+const SEEK_STEP_MS: i64 = 10_000;
+
+/// Lê uma sessão gravada com `--record`: o cabeçalho e todas as amostras.
+pub fn load(path: &Path) -> Result<(SystemInfo, Vec<Snapshot>), Box<dyn Error>> {
+    let mut lines = BufReader::new(File::open(path)?).lines().enumerate();
+
+    let header: SessionHeader = match lines.next() {
+        Some((_, line)) => serde_json::from_str(&line?)
+            .map_err(|e| format!("{} is not a monitor session: {}", path.display(), e))?,
+        None => return Err(format!("{} is empty", path.display()).into()),
+    };
+
+    let mut samples = Vec::new();
+    for (number, line) in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let sample: Snapshot = serde_json::from_str(&line)
+            .map_err(|e| format!("{}:{}: {}", path.display(), number + 1, e))?;
+        samples.push(sample);
+    }
+    if samples.is_empty() {
+        return Err(format!("{} has no samples", path.display()).into());
+    }
+
+    Ok((header.system, samples))
+}
+
+/// Mostra só o que vem nas amostras (de uma sessão gravada ou de um agente remoto).
+pub fn configure(first: &Snapshot, config: &mut AppConfig) {
+    config.no_gpu = first.gpus.is_empty();
+    config.no_network = first.network.is_none();
+    config.no_processes = true;
+    config.cgroups = false;
+    config.no_disks = true;
+    config.no_sensors = true;
+}
+
+/// Reproduz as amostras de uma sessão no ritmo em que foram gravadas.
+pub struct Replay {
+    samples: Vec<Snapshot>,
+    // Próxima amostra a ser aplicada
+    position: usize,
+    // Posição da reprodução, no relógio das amostras (ms desde a época Unix)
+    clock_ms: f64,
+    speed: f64,
+    paused: bool,
+    last_tick: Instant,
+}
+
+impl Replay {
+    pub fn new(samples: Vec<Snapshot>) -> Self {
+        let clock_ms = samples.first().map_or(0.0, |s| s.timestamp_ms as f64);
+        Self {
+            samples,
+            position: 0,
+            clock_ms,
+            speed: 1.0,
+            paused: false,
+            last_tick: Instant::now(),
+        }
+    }
+
+    /// Ajusta a configuração ao que foi gravado e esconde painéis sem dados.
+    pub fn configure(&self, config: &mut AppConfig) {
+        configure(&self.samples[0], config);
+    }
+
+    fn start_ms(&self) -> i64 {
+        self.samples[0].timestamp_ms
+    }
+
+    fn end_ms(&self) -> i64 {
+        self.samples[self.samples.len() - 1].timestamp_ms
+    }
+
+    /// Avança o relógio e aplica as amostras que já deveriam ter aparecido.
+    pub fn tick(&mut self, data: &mut SystemData) {
+        let elapsed = self.last_tick.elapsed().as_secs_f64() * 1000.0;
+        self.last_tick = Instant::now();
+        if !self.paused {
+            self.clock_ms = (self.clock_ms + elapsed * self.speed).min(self.end_ms() as f64);
+        }
+
+        while self.position < self.samples.len()
+            && self.samples[self.position].timestamp_ms as f64 <= self.clock_ms
+        {
+            data.apply(&self.samples[self.position]);
+            self.position += 1;
+        }
+        data.status = Some(self.status());
+    }
+
+    /// Pula para outro instante, reconstruindo o histórico dos gráficos até ele.
+    fn seek(&mut self, target_ms: i64, data: &mut SystemData) {
+        let target_ms = target_ms.clamp(self.start_ms(), self.end_ms());
+        self.clock_ms = target_ms as f64;
+        self.position = self
+            .samples
+            .partition_point(|sample| sample.timestamp_ms <= target_ms);
+
+        data.reset_graphs();
+        let first = self.position.saturating_sub(data.config.history);
+        for sample in &self.samples[first..self.position] {
+            data.apply(sample);
+        }
+        data.status = Some(self.status());
+    }
+
+    /// Teclas de reprodução; retorna `false` para as demais.
+    pub fn handle_key(&mut self, key: KeyEvent, data: &mut SystemData) -> bool {
+        let now = self.clock_ms as i64;
+        match key.code {
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Left => self.seek(now - SEEK_STEP_MS, data),
+            KeyCode::Right => self.seek(now + SEEK_STEP_MS, data),
+            KeyCode::Home => self.seek(self.start_ms(), data),
+            KeyCode::End => self.seek(self.end_ms(), data),
+            KeyCode::Char('1') => self.speed = 1.0,
+            KeyCode::Char('2') => self.speed = 2.0,
+            KeyCode::Char('0') => self.speed = 10.0,
+            _ => return false,
+        }
+        data.status = Some(self.status());
+        true
+    }
+
+    fn status(&self) -> String {
+        let seconds = |ms: f64| {
+            let total = (ms / 1000.0) as u64;
+            format!(
+                "{:02}:{:02}:{:02}",
+                total / 3600,
+                total / 60 % 60,
+                total % 60
+            )
+        };
+        let state = if self.paused {
+            "⏸"
+        } else if self.position >= self.samples.len() {
+            "■"
+        } else {
+            "▶"
+        };
+        format!(
+            "[REPLAY {} {}x {}/{}]",
+            state,
+            self.speed,
+            seconds(self.clock_ms - self.start_ms() as f64),
+            seconds((self.end_ms() - self.start_ms()) as f64)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::SessionRecorder;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn system_info() -> SystemInfo {
+        SystemInfo {
+            cpu_model: "Test CPU".to_string(),
+            cpu_cores: 1,
+            cpu_threads: 1,
+            gpu_model: "None".to_string(),
+            os_name: "Linux".to_string(),
+            os_version: "test".to_string(),
+        }
+    }
+
+    // Uma amostra por segundo, com o uso de CPU indicando a posição
+    fn sample(second: i64) -> Snapshot {
+        serde_json::from_str(&format!(
+            r#"{{"timestamp": "", "timestamp_ms": {},
+                "cpu": {{"usage": {}, "cores": []}},
+                "memory": {{"total": 8000, "used": 2000, "percent": 25.0}},
+                "swap": {{"total": 0, "used": 0, "percent": 0.0}}}}"#,
+            1_704_103_200_000 + second * 1000,
+            second * 10
+        ))
+        .unwrap()
+    }
+
+    fn session(name: &str, samples: usize) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("monitor-{}-{}.jsonl", name, std::process::id()));
+        let mut recorder = SessionRecorder::new(&path, system_info()).unwrap();
+        for second in 0..samples as i64 {
+            recorder.record(&sample(second)).unwrap();
+        }
+        path
+    }
+
+    fn load_error(path: &Path) -> String {
+        match load(path) {
+            Ok(_) => panic!("{} should not load", path.display()),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn loads_recorded_sessions() {
+        let path = session("replay-load", 3);
+        let (system, samples) = load(&path).unwrap();
+        assert_eq!(system.cpu_model, "Test CPU");
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[2].cpu.usage, 20.0);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_files_that_are_not_sessions() {
+        let path =
+            std::env::temp_dir().join(format!("monitor-replay-header-{}.csv", std::process::id()));
+        fs::write(&path, "").unwrap();
+        assert!(load_error(&path).ends_with("is empty"));
+
+        fs::write(&path, "timestamp,cpu_percent\n2024-01-01T10:00:00,5.0\n").unwrap();
+        assert!(load_error(&path).contains("is not a monitor session"));
+
+        let empty = session("replay-no-samples", 0);
+        assert!(load_error(&empty).ends_with("has no samples"));
+        fs::remove_file(path).unwrap();
+        fs::remove_file(empty).unwrap();
+    }
+
+    #[test]
+    fn errors_name_the_broken_line() {
+        let path = session("replay-broken", 2);
+        let mut contents = fs::read_to_string(&path).unwrap();
+        contents.push_str("{\"timestamp_ms\": \n");
+        fs::write(&path, contents).unwrap();
+        let error = load_error(&path);
+        assert!(
+            error.starts_with(&format!("{}:4: ", path.display())),
+            "{}",
+            error
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn seek_rebuilds_the_last_history_samples() {
+        let config = AppConfig {
+            history: 3,
+            ..AppConfig::default()
+        };
+        let mut data = SystemData::new(config, system_info()).unwrap();
+        let mut replay = Replay::new((0..10).map(sample).collect());
+        let cpu = |data: &SystemData| -> Vec<f64> {
+            data.graphs
+                .iter()
+                .find(|graph| graph.graph_type == crate::ChartKind::Cpu)
+                .unwrap()
+                .series[0]
+                .data
+                .iter()
+                .map(|&(_, usage)| usage)
+                .collect()
+        };
+
+        replay.seek(replay.start_ms() + 6000, &mut data);
+        assert_eq!(replay.position, 7);
+        assert_eq!(cpu(&data), [40.0, 50.0, 60.0]);
+
+        // Voltar para o início deixa só a primeira amostra (além do ponto inicial)
+        replay.seek(0, &mut data);
+        assert_eq!(replay.position, 1);
+        assert_eq!(cpu(&data), [0.0, 0.0]);
+    }
+}
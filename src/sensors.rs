@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::path::Path;
+use sysinfo::{ComponentExt, System, SystemExt};
+use tui::{
+    layout::Constraint,
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Row, Table},
+};
+
+use crate::sysfs::{self, read_string};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SensorKind {
+    /// °C
+    Temperature,
+    /// RPM
+    Fan,
+    /// V
+    Voltage,
+}
+
+/// Uma leitura de sensor; `high` e `critical` na mesma unidade do valor.
+#[derive(Clone, Debug)]
+pub struct Sensor {
+    pub chip: String,
+    pub label: String,
+    pub kind: SensorKind,
+    pub value: f64,
+    pub high: Option<f64>,
+    pub critical: Option<f64>,
+}
+
+impl Sensor {
+    /// Nome único usado nas séries do gráfico de temperatura.
+    pub fn name(&self) -> String {
+        format!("{}: {}", self.chip, self.label)
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.critical.is_some_and(|critical| self.value >= critical)
+    }
+
+    fn is_high(&self) -> bool {
+        self.high.is_some_and(|high| self.value >= high)
+    }
+
+    fn format(&self, value: f64) -> String {
+        match self.kind {
+            SensorKind::Temperature => format!("{:.1}°C", value),
+            SensorKind::Fan => format!("{:.0} RPM", value),
+            SensorKind::Voltage => format!("{:.3} V", value),
+        }
+    }
+}
+
+/// Sensores do hwmon no Linux; nos outros sistemas (ou sem hwmon), os `Components` do sysinfo.
+pub fn read_sensors(sys: &mut System, sysfs_root: &Path) -> Vec<Sensor> {
+    let sensors = read_hwmon(sysfs_root);
+    if !sensors.is_empty() {
+        return sensors;
+    }
+
+    sys.refresh_components_list();
+    sys.components()
+        .iter()
+        .map(|component| Sensor {
+            // Os rótulos do sysinfo são "<chip> <sensor>", ex.: "coretemp Core 0"
+            chip: component
+                .label()
+                .split_once(' ')
+                .map_or("sensors", |(chip, _)| chip)
+                .to_string(),
+            label: component
+                .label()
+                .split_once(' ')
+                .map_or(component.label(), |(_, label)| label)
+                .to_string(),
+            kind: SensorKind::Temperature,
+            value: component.temperature() as f64,
+            high: Some(component.max() as f64).filter(|max| *max > 0.0),
+            critical: component.critical().map(|c| c as f64),
+        })
+        .collect()
+}
+
+/// Lê `<root>/class/hwmon/hwmonN`: temperaturas, ventoinhas e tensões de cada chip.
+pub fn read_hwmon(sysfs_root: &Path) -> Vec<Sensor> {
+    let chips = sysfs::numbered_entries(&sysfs_root.join("class/hwmon"), "hwmon");
+
+    // Chips repetidos (ex.: dois SSDs NVMe) ganham o nome do hwmon para se distinguirem
+    let names: Vec<String> = chips
+        .iter()
+        .map(|(_, path)| read_string(&path.join("name")).unwrap_or_else(|| "hwmon".to_string()))
+        .collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for name in &names {
+        *counts.entry(name).or_default() += 1;
+    }
+
+    let mut sensors = Vec::new();
+    for ((index, path), name) in chips.iter().zip(&names) {
+        let chip = if counts[name.as_str()] > 1 {
+            format!("{} (hwmon{})", name, index)
+        } else {
+            name.clone()
+        };
+        for (prefix, kind, scale) in [
+            ("temp", SensorKind::Temperature, 1000.0),
+            ("fan", SensorKind::Fan, 1.0),
+            ("in", SensorKind::Voltage, 1000.0),
+        ] {
+            sensors.extend(read_channels(path, &chip, prefix, kind, scale));
+        }
+    }
+    sensors
+}
+
+/// Canais `<prefix>N_input` de um chip, com rótulo e limites quando existirem.
+fn read_channels(
+    path: &Path,
+    chip: &str,
+    prefix: &str,
+    kind: SensorKind,
+    scale: f64,
+) -> Vec<Sensor> {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut numbers: Vec<u32> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_prefix(prefix)?
+                .strip_suffix("_input")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    numbers.sort();
+
+    let value = |channel: &str, field: &str| -> Option<f64> {
+        let raw: f64 = read_string(&path.join(format!("{}_{}", channel, field)))?
+            .parse()
+            .ok()?;
+        Some(raw / scale)
+    };
+
+    numbers
+        .into_iter()
+        .filter_map(|number| {
+            let channel = format!("{}{}", prefix, number);
+            Some(Sensor {
+                chip: chip.to_string(),
+                label: read_string(&path.join(format!("{}_label", channel)))
+                    .unwrap_or_else(|| channel.clone()),
+                kind,
+                value: value(&channel, "input")?,
+                high: value(&channel, "max"),
+                critical: value(&channel, "crit"),
+            })
+        })
+        .collect()
+}
+
+/// Linhas que o painel ocupa: um título por chip e uma linha por sensor.
+pub fn panel_rows(sensors: &[Sensor]) -> usize {
+    let mut chips: Vec<&str> = sensors.iter().map(|s| s.chip.as_str()).collect();
+    chips.dedup();
+    chips.len() + sensors.len()
+}
+
+pub fn draw_sensors(sensors: &[Sensor]) -> Table<'static> {
+    let header = Row::new(vec!["Sensor", "Value", "High", "Crit"])
+        .style(Style::default().fg(Color::LightRed));
+
+    let mut rows = Vec::new();
+    let mut chip = None;
+    for sensor in sensors {
+        if chip != Some(&sensor.chip) {
+            chip = Some(&sensor.chip);
+            rows.push(Row::new(vec![Cell::from(sensor.chip.clone()).style(
+                Style::default()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD),
+            )]));
+        }
+
+        let value_style = if sensor.is_critical() {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else if sensor.is_high() {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let limit = |limit: Option<f64>| limit.map(|v| sensor.format(v)).unwrap_or_default();
+        rows.push(Row::new(vec![
+            Cell::from(format!("  {}", sensor.label)),
+            Cell::from(sensor.format(sensor.value)).style(value_style),
+            Cell::from(limit(sensor.high)).style(Style::default().fg(Color::Gray)),
+            Cell::from(limit(sensor.critical)).style(Style::default().fg(Color::Red)),
+        ]));
+    }
+
+    Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    "Sensors",
+                    Style::default()
+                        .fg(Color::LightRed)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::LightRed)),
+        )
+        .widths(&[
+            Constraint::Min(14),
+            Constraint::Length(11),
+            Constraint::Length(9),
+            Constraint::Length(9),
+        ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_hwmon_chips() {
+        let root = sysfs::fixture(
+            "sensors",
+            &[
+                ("class/hwmon/hwmon0/name", "coretemp\n"),
+                ("class/hwmon/hwmon0/temp1_input", "64000\n"),
+                ("class/hwmon/hwmon0/temp1_label", "Package id 0\n"),
+                ("class/hwmon/hwmon0/temp1_max", "80000\n"),
+                ("class/hwmon/hwmon0/temp1_crit", "100000\n"),
+                ("class/hwmon/hwmon0/temp2_input", "101000\n"),
+                ("class/hwmon/hwmon0/temp2_label", "Core 0\n"),
+                ("class/hwmon/hwmon0/temp2_crit", "100000\n"),
+                ("class/hwmon/hwmon1/name", "nct6775\n"),
+                ("class/hwmon/hwmon1/fan1_input", "1200\n"),
+                ("class/hwmon/hwmon1/in0_input", "1032\n"),
+                ("class/hwmon/hwmon2/name", "nvme\n"),
+                ("class/hwmon/hwmon2/temp1_input", "38850\n"),
+                ("class/hwmon/hwmon3/name", "nvme\n"),
+                ("class/hwmon/hwmon3/temp1_input", "41850\n"),
+            ],
+        );
+
+        let sensors = read_hwmon(&root);
+        let names: Vec<String> = sensors.iter().map(|s| s.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "coretemp: Package id 0",
+                "coretemp: Core 0",
+                "nct6775: fan1",
+                "nct6775: in0",
+                "nvme (hwmon2): temp1",
+                "nvme (hwmon3): temp1",
+            ]
+        );
+
+        assert_eq!(sensors[0].value, 64.0);
+        assert_eq!(sensors[0].high, Some(80.0));
+        assert!(!sensors[0].is_critical());
+        assert!(sensors[1].is_critical());
+        assert_eq!(sensors[2].kind, SensorKind::Fan);
+        assert_eq!(sensors[2].value, 1200.0);
+        assert_eq!(sensors[3].kind, SensorKind::Voltage);
+        assert!((sensors[3].value - 1.032).abs() < 1e-9);
+        assert_eq!(panel_rows(&sensors), 10);
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cgroup::CgroupStats;
+use crate::cpufreq::ThrottleReason;
+use crate::gpu::GpuStats;
+use crate::network::InterfaceStats;
+use crate::pressure::PressureStats;
+use crate::procstat::{CpuBreakdown, KernelActivity};
+use crate::rapl::{CpuPower, SessionEnergy};
+
+/// Uma amostra completa do sistema, usada pelas saídas sem TUI.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Horário da amostra em RFC 3339
+    pub timestamp: String,
+    /// Milissegundos desde a época Unix
+    pub timestamp_ms: i64,
+    /// Load, uptime e contadores do kernel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemSnapshot>,
+    pub cpu: CpuSnapshot,
+    pub memory: MemorySnapshot,
+    pub swap: MemorySnapshot,
+    /// PSI de CPU, memória e I/O (Linux 4.20+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pressure: Option<PressureStats>,
+    /// Limites do cgroup v2 usados nos percentuais (com --cgroup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<CgroupStats>,
+    /// Uma entrada por GPU, na ordem dos backends
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpus: Vec<GpuStats>,
+    /// Energia consumida desde o início da sessão (RAPL e GPUs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub energy: Option<SessionEnergy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkSnapshot>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    /// Médias de 1, 5 e 15 minutos
+    pub load: [f64; 3],
+    /// Segundos desde o boot
+    pub uptime: u64,
+    /// Horário do boot em segundos desde a época Unix
+    pub boot_time: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activity: Option<KernelActivity>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CpuSnapshot {
+    pub usage: f64,
+    pub cores: Vec<f64>,
+    /// Consumo pelo RAPL, quando legível
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<CpuPower>,
+    /// Clock atual de cada núcleo em MHz (cpufreq)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frequencies_mhz: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throttling: Option<ThrottleReason>,
+    /// Divisão do tempo de CPU (/proc/stat), global e por CPU
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub times: Option<CpuBreakdown>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub core_times: Vec<CpuBreakdown>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MemorySnapshot {
+    pub total: u64,
+    pub used: u64,
+    #[serde(default)]
+    pub available: u64,
+    pub percent: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkSnapshot {
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+    pub rx_total: u64,
+    pub tx_total: u64,
+    pub interfaces: Vec<InterfaceStats>,
+}
+
+pub fn percent(used: u64, total: u64) -> f64 {
+    if total > 0 {
+        (used as f64 / total as f64) * 100.0
+    } else {
+        0.0
+    }
+}
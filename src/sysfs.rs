@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Raiz padrão do sysfs; os leitores recebem a raiz para poderem ler fixtures.
+pub const DEFAULT_ROOT: &str = "/sys";
+
+pub fn read_string(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+pub fn read_number(path: &Path) -> Option<u64> {
+    read_string(path)?.parse().ok()
+}
+
+/// Entradas de `dir` cujo nome é `<prefix><número>`, ordenadas pelo número.
+pub fn numbered_entries(dir: &Path, prefix: &str) -> Vec<(u32, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut numbered: Vec<(u32, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let index = name.strip_prefix(prefix)?.parse().ok()?;
+            Some((index, entry.path()))
+        })
+        .collect();
+    numbered.sort();
+    numbered
+}
+
+/// Temperaturas (rótulo, °C) de um diretório hwmon; sem `tempN_label` o rótulo é `tempN`.
+pub fn hwmon_temperatures(hwmon: &Path) -> Vec<(String, f64)> {
+    let Ok(entries) = fs::read_dir(hwmon) else {
+        return Vec::new();
+    };
+    let mut sensors: Vec<(u32, String, f64)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let sensor = name.strip_suffix("_input")?;
+            let index = sensor.strip_prefix("temp")?.parse().ok()?;
+            // Valores em miligraus
+            let millidegrees: i64 = read_string(&entry.path())?.parse().ok()?;
+            let label = read_string(&hwmon.join(format!("{}_label", sensor)))
+                .unwrap_or_else(|| sensor.to_string());
+            Some((index, label, millidegrees as f64 / 1000.0))
+        })
+        .collect();
+    sensors.sort_by_key(|(index, _, _)| *index);
+    sensors
+        .into_iter()
+        .map(|(_, label, celsius)| (label, celsius))
+        .collect()
+}
+
+/// Cria uma árvore de arquivos temporária para os testes de leitores do sysfs.
+#[cfg(test)]
+pub fn fixture(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("monitor-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for (path, contents) in files {
+        write_fixture(&root, path, contents);
+    }
+    root
+}
+
+#[cfg(test)]
+pub fn write_fixture(root: &Path, path: &str, contents: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}